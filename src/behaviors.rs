@@ -0,0 +1,21 @@
+mod brain;
+mod hunt;
+mod policy;
+mod rheotaxis;
+mod scent;
+mod schooling;
+mod steering_behavior;
+mod targets;
+mod walls;
+mod wander;
+
+pub use brain::*;
+pub use hunt::*;
+pub use policy::*;
+pub use rheotaxis::*;
+pub use scent::*;
+pub use schooling::*;
+pub use steering_behavior::*;
+pub use targets::*;
+pub use walls::*;
+pub use wander::*;
@@ -0,0 +1,106 @@
+use crate::behaviors::{Desire, Environment, SteeringBehavior, Swimmer, Target, Who};
+use crate::components::{Brain, Position, Rotation};
+use crate::constants::{BRAIN_CROWD, BRAIN_TURN, BRAIN_WALL_PROBE, SCHOOLING_PRIORITY};
+use crate::resources::TankShape;
+use crate::utils::{distance_to_circle_wall, distance_to_square_wall};
+use bevy::math::Vec2;
+
+/// swimmers with a brain steer by it instead of the rules. the first output is the turn as a
+/// fraction of max_turn, the second the speed, which only reynolds kinematics can change. evolve
+/// uses reynolds kinematics for that reason, and says so in the species file it writes
+pub struct Think {
+    pub who: Who,
+    pub max_turn: f32,
+    pub priority: u8,
+}
+
+impl Default for Think {
+    fn default() -> Self {
+        Think {
+            who: Who::All,
+            max_turn: BRAIN_TURN,
+            priority: SCHOOLING_PRIORITY,
+        }
+    }
+}
+
+impl SteeringBehavior for Think {
+    fn name(&self) -> &'static str {
+        "brain"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn with_brain(&self) -> bool {
+        true
+    }
+
+    fn steer(&self, agent: &Swimmer, neighbors: &[&Swimmer], env: &Environment) -> Option<Desire> {
+        if !self.who.matches(agent) {
+            return None;
+        }
+        let brain = env.brains.get(&agent.entity)?;
+        let [turn, speed] = brain.think(&senses(agent, neighbors, env));
+        Some(Desire {
+            speed: (speed + 1.0) / 2.0,
+            ..Desire::heading(agent.rotation + Rotation::new(turn * self.max_turn))
+        })
+    }
+}
+
+/// what a brain knows about its surroundings, relative to its own heading and scaled by its
+/// vision so every input is roughly in [-1, 1]. directions are given as a sine and cosine so
+/// there's no jump behind the swimmer. anything out of sight reads as zero
+///
+/// - how crowded it is, counting only its own kind
+/// - the direction of and closeness to the nearest of its kind
+/// - the average heading of its kind, and the direction to their center
+/// - closeness of the wall straight ahead, ahead-left and ahead-right
+/// - the direction of and closeness to the nearest predator in sight range, sensed all around
+pub fn senses(agent: &Swimmer, neighbors: &[&Swimmer], env: &Environment) -> [f32; Brain::INPUTS] {
+    let (p, r, reach) = (agent.position, agent.rotation, agent.vision.distance);
+    let bearing = |q: Position| {
+        let angle = (p.point_towards(q) - r).0;
+        [angle.sin(), angle.cos()]
+    };
+    let closeness = |d: f32| (1.0 - d / reach).clamp(0.0, 1.0);
+
+    let kind = neighbors
+        .iter()
+        .filter(|n| n.predator == agent.predator)
+        .collect::<Vec<_>>();
+    let mut senses = [0.0; Brain::INPUTS];
+    senses[0] = (kind.len() as f32 / BRAIN_CROWD).min(1.0);
+    let nearest = kind
+        .iter()
+        .min_by(|a, b| p.distance(a.position).total_cmp(&p.distance(b.position)));
+    if let Some(n) = nearest {
+        [senses[1], senses[2]] = bearing(n.position);
+        senses[3] = closeness(p.distance(n.position));
+    }
+    if !kind.is_empty() {
+        let heading = kind.iter().map(|n| n.rotation.unit_vector()).sum::<Vec2>();
+        let angle = (Rotation::new(heading.y.atan2(heading.x)) - r).0;
+        [senses[4], senses[5]] = [angle.sin(), angle.cos()];
+        let center = kind.iter().map(|n| n.position.0).sum::<Vec2>() / kind.len() as f32;
+        [senses[6], senses[7]] = bearing(Position(center));
+    }
+    let wall = |probe: f32| {
+        let probe = r + Rotation::new(probe);
+        closeness(match env.tank.shape {
+            TankShape::Circle { radius } => distance_to_circle_wall(p, probe, radius),
+            TankShape::Rectangle { bounds } => distance_to_square_wall(p, probe, bounds),
+        })
+    };
+    [senses[8], senses[9], senses[10]] =
+        [wall(0.0), wall(BRAIN_WALL_PROBE), wall(-BRAIN_WALL_PROBE)];
+    if !agent.predator {
+        if let Some((q, _)) = env.resolve(Target::NearestPredator(reach), agent, neighbors) {
+            [senses[11], senses[12]] = bearing(q);
+            senses[13] = closeness(p.distance(q));
+        }
+    }
+    senses
+}
@@ -0,0 +1,120 @@
+use crate::behaviors::{predict, Desire, Environment, SteeringBehavior, Swimmer, Who};
+use crate::components::{Position, Role};
+use crate::constants::{HERD_DISTANCE, HUNT_PRIORITY, ISOLATION_RADIUS};
+use bevy::math::Vec2;
+use bevy::prelude::Resource;
+use std::cmp::Ordering;
+use std::f32::consts::PI;
+
+/// which of the fish a shark can see it goes after when it picks a new target
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PreySelection {
+    #[default]
+    Nearest,
+    // the fish with the fewest other fish around it
+    Isolated,
+    Smallest,
+}
+
+/// sharks steer to intercept their target, leading it by its heading and speed. swimmers with a
+/// hunt state only hunt while stalking or attacking and keep the target pick_prey (or their pack)
+/// gave them. anything else that hunts picks the best fish in sight afresh every tick
+pub struct Hunt {
+    pub who: Who,
+    pub max_turn: f32,
+    pub priority: u8,
+}
+
+impl Default for Hunt {
+    fn default() -> Self {
+        Hunt {
+            who: Who::Sharks,
+            max_turn: PI,
+            priority: HUNT_PRIORITY,
+        }
+    }
+}
+
+impl Hunt {
+    // herders head for a point on the far side of the prey from the striker, then close in once
+    // they're around it
+    fn flank(agent: &Swimmer, prey: &Swimmer, striker: Option<&Swimmer>, angle: f32) -> Position {
+        let Some(striker) = striker else {
+            return prey.position;
+        };
+        let away = (prey.position.0 - striker.position.0).normalize_or_zero();
+        let flank =
+            Position(prey.position.0 + Vec2::from_angle(angle).rotate(away) * HERD_DISTANCE);
+        if agent.position.distance(flank) < HERD_DISTANCE / 2.0 {
+            prey.position
+        } else {
+            flank
+        }
+    }
+}
+
+fn company(prey: &Swimmer, env: &Environment) -> usize {
+    env.index
+        .nearby(prey.position.0, ISOLATION_RADIUS)
+        .filter_map(|(e, _)| env.swimmers.get(&e))
+        .filter(|s| !s.predator && s.entity != prey.entity)
+        .filter(|s| prey.position.distance(s.position) < ISOLATION_RADIUS)
+        .count()
+}
+
+/// the fish in sight that the environment's prey selection prefers
+pub fn choose_prey<'a>(
+    agent: &Swimmer,
+    neighbors: &[&'a Swimmer],
+    env: &Environment,
+) -> Option<&'a Swimmer> {
+    let distance = |s: &Swimmer| agent.position.distance(s.position);
+    let closer = |a: &&Swimmer, b: &&Swimmer| distance(a).total_cmp(&distance(b));
+    let prey = neighbors.iter().copied().filter(|n| !n.predator);
+    match env.selection {
+        PreySelection::Nearest => prey.min_by(closer),
+        PreySelection::Isolated => prey
+            .map(|p| (company(p, env), p))
+            .min_by(|(a, pa), (b, pb)| a.cmp(b).then_with(|| closer(pa, pb)))
+            .map(|(_, p)| p),
+        PreySelection::Smallest => prey.min_by(|a, b| match a.size.0.total_cmp(&b.size.0) {
+            Ordering::Equal => closer(a, b),
+            o => o,
+        }),
+    }
+}
+
+impl SteeringBehavior for Hunt {
+    fn name(&self) -> &'static str {
+        "hunt"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn steer(&self, agent: &Swimmer, neighbors: &[&Swimmer], env: &Environment) -> Option<Desire> {
+        if !self.who.matches(agent) || agent.hunter.is_some_and(|h| !h.state.hunting()) {
+            return None;
+        }
+        let aim = match agent.hunter {
+            Some(h) => {
+                let prey = env.swimmers.get(&h.target?)?;
+                match h.role {
+                    Role::Herder { striker, angle } => {
+                        Self::flank(agent, prey, env.swimmers.get(&striker), angle)
+                    }
+                    _ => predict(agent, prey.position, prey.velocity),
+                }
+            }
+            None => {
+                let prey = choose_prey(agent, neighbors, env)?;
+                predict(agent, prey.position, prey.velocity)
+            }
+        };
+        let turn = agent
+            .position
+            .steer_towards(aim, agent.rotation, self.max_turn);
+        Some(Desire::heading(agent.rotation + turn))
+    }
+}
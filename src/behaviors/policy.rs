@@ -0,0 +1,46 @@
+use crate::behaviors::{Desire, Environment, SteeringBehavior, Swimmer, Who};
+use crate::components::Rotation;
+use crate::constants::{BRAIN_TURN, SCHOOLING_PRIORITY};
+
+/// swimmers with an action in Actions do as they're told, the same way a brain's outputs are used
+pub struct Act {
+    pub who: Who,
+    pub max_turn: f32,
+    pub priority: u8,
+}
+
+impl Default for Act {
+    fn default() -> Self {
+        Act {
+            who: Who::All,
+            max_turn: BRAIN_TURN,
+            priority: SCHOOLING_PRIORITY,
+        }
+    }
+}
+
+impl SteeringBehavior for Act {
+    fn name(&self) -> &'static str {
+        "policy"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn with_brain(&self) -> bool {
+        true
+    }
+
+    fn steer(&self, agent: &Swimmer, _: &[&Swimmer], env: &Environment) -> Option<Desire> {
+        if !self.who.matches(agent) {
+            return None;
+        }
+        let action = env.actions.0.get(&agent.entity)?;
+        let turn = action.turn.clamp(-1.0, 1.0) * self.max_turn;
+        Some(Desire {
+            speed: action.speed.clamp(0.0, 1.0),
+            ..Desire::heading(agent.rotation + Rotation::new(turn))
+        })
+    }
+}
@@ -0,0 +1,50 @@
+use crate::behaviors::{Desire, Environment, SteeringBehavior, Swimmer, Who};
+use crate::components::Rotation;
+use crate::constants::{RHEOTAXIS, SCHOOLING_PRIORITY};
+use bevy::math::Vec2;
+
+/// turn to face into the current, the way fish hold station in a river. the stronger the current
+/// relative to the swimmer's speed, the more it counts
+pub struct Rheotaxis {
+    pub who: Who,
+    pub max_turn: f32,
+    pub priority: u8,
+}
+
+impl Default for Rheotaxis {
+    fn default() -> Self {
+        Rheotaxis {
+            who: Who::Fish,
+            max_turn: RHEOTAXIS,
+            priority: SCHOOLING_PRIORITY,
+        }
+    }
+}
+
+impl SteeringBehavior for Rheotaxis {
+    fn name(&self) -> &'static str {
+        "rheotaxis"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn steer(&self, agent: &Swimmer, _: &[&Swimmer], env: &Environment) -> Option<Desire> {
+        if !self.who.matches(agent) || agent.fleeing || env.flow.is_still() {
+            return None;
+        }
+        let current = env.flow.at(agent.position.0);
+        if current == Vec2::ZERO || agent.speed.0 <= 0.0 {
+            return None;
+        }
+        let upstream = Rotation::new((-current.y).atan2(-current.x));
+        let turn = (upstream - agent.rotation)
+            .0
+            .clamp(-self.max_turn, self.max_turn);
+        Some(Desire {
+            weight: (current.length() / agent.speed.0).min(1.0),
+            ..Desire::heading(agent.rotation + Rotation::new(turn))
+        })
+    }
+}
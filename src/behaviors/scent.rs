@@ -0,0 +1,41 @@
+use crate::behaviors::{Desire, Environment, SteeringBehavior, Swimmer, Who};
+use crate::components::Rotation;
+use crate::constants::SCENT_THRESHOLD;
+use crate::resources::Chemical;
+
+/// turn up a scent gradient, or down it when avoid is set. following a scent is for calm fish,
+/// avoiding one works even while fleeing
+pub struct FollowScent {
+    pub name: &'static str,
+    pub who: Who,
+    pub chemical: Chemical,
+    pub avoid: bool,
+    pub max_turn: f32,
+    pub priority: u8,
+}
+
+impl SteeringBehavior for FollowScent {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn steer(&self, agent: &Swimmer, _: &[&Swimmer], env: &Environment) -> Option<Desire> {
+        if !self.who.matches(agent) || (agent.fleeing && !self.avoid) {
+            return None;
+        }
+        let mut gradient = env.scents.field(self.chemical).gradient(agent.position.0);
+        if gradient.length() < SCENT_THRESHOLD {
+            return None;
+        }
+        if self.avoid {
+            gradient = -gradient;
+        }
+        let rel = Rotation::new(gradient.y.atan2(gradient.x)) - agent.rotation;
+        let turn = rel.0.clamp(-self.max_turn, self.max_turn);
+        Some(Desire::heading(agent.rotation + Rotation::new(turn)))
+    }
+}
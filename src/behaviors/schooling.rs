@@ -0,0 +1,160 @@
+use crate::behaviors::{Desire, Environment, SteeringBehavior, Swimmer, Who};
+use crate::components::{Position, Rotation};
+use crate::constants::{
+    COLOR_PREFERENCE, OTHER_SPECIES_AFFINITY, SCHOOLING_PRIORITY, SEPARATION_PRIORITY,
+    SIZE_PREFERENCE,
+};
+use bevy::math::Vec2;
+
+/// how much one fish treats another as part of its school, from 0 to 1
+pub fn similarity(a: &Swimmer, b: &Swimmer) -> f32 {
+    let species = if a.species == b.species {
+        1.0
+    } else {
+        OTHER_SPECIES_AFFINITY
+    };
+    let size = a.size.0.min(b.size.0) / a.size.0.max(b.size.0);
+    let hue = (a.color.0.h() - b.color.0.h()).abs();
+    let color = 1.0 - hue.min(360.0 - hue) / 180.0;
+    species * size.powf(SIZE_PREFERENCE) * color.powf(COLOR_PREFERENCE)
+}
+
+// fish school with fish and sharks with sharks. fleeing swimmers don't school at all
+fn schoolmates<'a>(
+    who: Who,
+    agent: &Swimmer,
+    neighbors: &'a [&'a Swimmer],
+) -> Option<impl Iterator<Item = &'a Swimmer> + Clone> {
+    if !who.matches(agent) || agent.fleeing {
+        return None;
+    }
+    let predator = agent.predator;
+    let mut mates = neighbors
+        .iter()
+        .copied()
+        .filter(move |n| n.predator == predator)
+        .peekable();
+    mates.peek()?;
+    Some(mates)
+}
+
+/// point away from visible schoolmates, whatever they are
+pub struct Separation {
+    pub who: Who,
+    pub priority: u8,
+}
+
+impl Default for Separation {
+    fn default() -> Self {
+        Separation {
+            who: Who::Fish,
+            priority: SEPARATION_PRIORITY,
+        }
+    }
+}
+
+impl SteeringBehavior for Separation {
+    fn name(&self) -> &'static str {
+        "separation"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn steer(&self, agent: &Swimmer, neighbors: &[&Swimmer], _: &Environment) -> Option<Desire> {
+        let mates = schoolmates(self.who, agent, neighbors)?;
+        let (p1, r1) = (agent.position, agent.rotation);
+        let mut r = r1;
+        for n in mates {
+            r += p1.steer_away(n.position, r1, agent.weights.separation);
+        }
+        Some(Desire::heading(r))
+    }
+}
+
+/// point in the same direction as visible friends, weighted by how similar they are
+pub struct Alignment {
+    pub who: Who,
+    pub priority: u8,
+}
+
+impl Default for Alignment {
+    fn default() -> Self {
+        Alignment {
+            who: Who::Fish,
+            priority: SCHOOLING_PRIORITY,
+        }
+    }
+}
+
+impl SteeringBehavior for Alignment {
+    fn name(&self) -> &'static str {
+        "alignment"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn steer(&self, agent: &Swimmer, neighbors: &[&Swimmer], _: &Environment) -> Option<Desire> {
+        let mates = schoolmates(self.who, agent, neighbors)?;
+        let max = agent.weights.alignment;
+        let mut turn = 0.0;
+        for n in mates {
+            let rel = n.rotation - agent.rotation;
+            turn += similarity(agent, n) * rel.0.clamp(-max, max);
+        }
+        Some(Desire::heading(agent.rotation + Rotation::new(turn)))
+    }
+}
+
+/// point towards the center of visible friends, weighted by how similar they are
+pub struct Cohesion {
+    pub who: Who,
+    pub priority: u8,
+}
+
+impl Default for Cohesion {
+    fn default() -> Self {
+        Cohesion {
+            who: Who::Fish,
+            priority: SCHOOLING_PRIORITY,
+        }
+    }
+}
+
+impl SteeringBehavior for Cohesion {
+    fn name(&self) -> &'static str {
+        "cohesion"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn steer(&self, agent: &Swimmer, neighbors: &[&Swimmer], _: &Environment) -> Option<Desire> {
+        let mates = schoolmates(self.who, agent, neighbors)?;
+        let count = mates.clone().count();
+        let mut center = Vec2::default();
+        let mut total = 0.0;
+        for n in mates {
+            let weight = similarity(agent, n);
+            center += n.position.0 * weight;
+            total += weight;
+        }
+        if total == 0.0 {
+            return None;
+        }
+        center /= total;
+        let turn =
+            agent
+                .position
+                .steer_towards(Position(center), agent.rotation, agent.weights.cohesion);
+        // a crowd of strangers pulls less than the same number of close relatives
+        Some(Desire {
+            weight: total / count as f32,
+            ..Desire::heading(agent.rotation + turn)
+        })
+    }
+}
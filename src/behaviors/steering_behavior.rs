@@ -0,0 +1,181 @@
+use crate::behaviors::PreySelection;
+use crate::components::{
+    Brain, Coloring, Hunter, Noise, Position, Rotation, Size, SpeciesId, Speed, Vision, Weights,
+};
+use crate::constants::FOOD_SIZE;
+use crate::resources::{Actions, Confusion, Flow, Scents, SpatialIndex, Tank};
+use crate::utils::can_see_position;
+use bevy::math::Vec2;
+use bevy::prelude::{App, Entity, Resource};
+use bevy::utils::HashMap;
+use rand::rngs::StdRng;
+use std::cell::RefCell;
+
+/// a snapshot of one swimmer, taken before any behaviors run this tick
+#[derive(Clone, Copy, Debug)]
+pub struct Swimmer {
+    pub entity: Entity,
+    pub species: SpeciesId,
+    pub predator: bool,
+    pub fleeing: bool,
+    // None for swimmers that don't hunt
+    pub hunter: Option<Hunter>,
+    pub position: Position,
+    pub rotation: Rotation,
+    pub velocity: Vec2,
+    pub speed: Speed,
+    pub size: Size,
+    pub vision: Vision,
+    pub weights: Weights,
+    pub noise: Noise,
+    pub color: Coloring,
+}
+
+/// everything about the tank a behavior might need besides the swimmer and its neighbors
+pub struct Environment<'a> {
+    pub tank: &'a Tank,
+    pub index: &'a SpatialIndex,
+    pub swimmers: &'a HashMap<Entity, Swimmer>,
+    pub food: &'a HashMap<Entity, (Position, Size)>,
+    pub confusion: Confusion,
+    pub selection: PreySelection,
+    pub scents: &'a Scents,
+    pub flow: &'a Flow,
+    // only swimmers that have a brain are in here
+    pub brains: &'a HashMap<Entity, &'a Brain>,
+    pub actions: &'a Actions,
+    // the simulation's random numbers, for behaviors that draw any
+    pub rng: &'a RefCell<&'a mut StdRng>,
+}
+
+/// what a behavior wants: a heading, how fast to go that way as a fraction of max speed (only
+/// reynolds kinematics can slow down), and how much it counts against other behaviors at the same
+/// priority
+#[derive(Clone, Copy, Debug)]
+pub struct Desire {
+    pub heading: Rotation,
+    pub speed: f32,
+    pub weight: f32,
+}
+
+impl Desire {
+    pub fn heading(heading: Rotation) -> Desire {
+        Desire {
+            heading,
+            speed: 1.0,
+            weight: 1.0,
+        }
+    }
+}
+
+/// which swimmers a built in behavior applies to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Who {
+    #[default]
+    All,
+    Fish,
+    Sharks,
+}
+
+impl Who {
+    pub fn matches(self, swimmer: &Swimmer) -> bool {
+        match self {
+            Who::All => true,
+            Who::Fish => !swimmer.predator,
+            Who::Sharks => swimmer.predator,
+        }
+    }
+}
+
+/// something to steer towards or away from
+#[derive(Clone, Copy, Debug)]
+pub enum Target {
+    Point(Vec2),
+    // the closest pellet the swimmer can see
+    NearestFood,
+    // the closest fish the swimmer can see
+    NearestPrey,
+    // the closest shark within the given distance. threats are sensed all around, not only in the
+    // vision cone
+    NearestPredator(f32),
+}
+
+impl Environment<'_> {
+    /// where the target is and how fast it's moving
+    pub fn resolve(
+        &self,
+        target: Target,
+        agent: &Swimmer,
+        neighbors: &[&Swimmer],
+    ) -> Option<(Position, Vec2)> {
+        let nearest = |a: &Position, b: &Position| {
+            agent
+                .position
+                .distance(*a)
+                .total_cmp(&agent.position.distance(*b))
+        };
+        match target {
+            Target::Point(p) => Some((Position(p), Vec2::ZERO)),
+            Target::NearestFood => self
+                .index
+                .nearby(agent.position.0, agent.vision.distance / FOOD_SIZE)
+                .filter_map(|(e, _)| self.food.get(&e))
+                .filter(|(p, s)| {
+                    can_see_position(agent.position, agent.rotation, agent.vision, *s, *p)
+                })
+                .map(|(p, _)| *p)
+                .min_by(nearest)
+                .map(|p| (p, Vec2::ZERO)),
+            Target::NearestPrey => neighbors
+                .iter()
+                .filter(|n| !n.predator)
+                .min_by(|a, b| nearest(&a.position, &b.position))
+                .map(|n| (n.position, n.velocity)),
+            Target::NearestPredator(range) => self
+                .swimmers
+                .values()
+                .filter(|s| s.predator && s.entity != agent.entity)
+                .filter(|s| agent.position.distance(s.position) <= range)
+                .min_by(|a, b| nearest(&a.position, &b.position))
+                .map(|s| (s.position, s.velocity)),
+        }
+    }
+}
+
+/// a rule that turns a swimmer. behaviors only see a snapshot of the tank and say what they'd like
+/// the swimmer to do, steering weighs all of them up by priority
+pub trait SteeringBehavior: Send + Sync + 'static {
+    /// shown in the inspector and the steering overlay
+    fn name(&self) -> &'static str;
+
+    /// higher priorities get first claim on a swimmer's turn rate (or max force)
+    fn priority(&self) -> u8;
+
+    /// whether this still runs for swimmers steered by a brain or an outside policy. most rules
+    /// don't, the brain or policy does their job
+    fn with_brain(&self) -> bool {
+        false
+    }
+
+    /// neighbors are the swimmers the agent can see. None means this behavior has no opinion
+    fn steer(&self, agent: &Swimmer, neighbors: &[&Swimmer], env: &Environment) -> Option<Desire>;
+}
+
+/// every behavior that runs on every swimmer each tick
+#[derive(Resource, Default)]
+pub struct SteeringBehaviors(pub Vec<Box<dyn SteeringBehavior>>);
+
+pub trait SteeringAppExt {
+    fn add_steering_behavior(&mut self, behavior: impl SteeringBehavior) -> &mut Self;
+}
+
+impl SteeringAppExt for App {
+    fn add_steering_behavior(&mut self, behavior: impl SteeringBehavior) -> &mut Self {
+        self.init_resource::<SteeringBehaviors>();
+        self.world
+            .resource_mut::<SteeringBehaviors>()
+            .0
+            .push(Box::new(behavior));
+        self
+    }
+}
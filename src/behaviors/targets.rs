@@ -0,0 +1,167 @@
+use crate::behaviors::{Desire, Environment, SteeringBehavior, Swimmer, Target, Who};
+use crate::components::Position;
+use crate::utils::intercept;
+use bevy::math::Vec2;
+
+// where to head to meet a moving target. if it can't be caught, head for where it is now
+pub fn predict(agent: &Swimmer, position: Position, velocity: Vec2) -> Position {
+    intercept(agent.position, agent.speed.0, position, velocity).unwrap_or(position)
+}
+
+/// turn towards a target, no more than max_turn per tick. fleeing swimmers have better things to do
+pub struct Seek {
+    pub name: &'static str,
+    pub who: Who,
+    pub target: Target,
+    pub max_turn: f32,
+    pub priority: u8,
+}
+
+impl SteeringBehavior for Seek {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn steer(&self, agent: &Swimmer, neighbors: &[&Swimmer], env: &Environment) -> Option<Desire> {
+        if !self.who.matches(agent) || agent.fleeing {
+            return None;
+        }
+        let (target, _) = env.resolve(self.target, agent, neighbors)?;
+        let turn = agent
+            .position
+            .steer_towards(target, agent.rotation, self.max_turn);
+        Some(Desire::heading(agent.rotation + turn))
+    }
+}
+
+/// turn directly away from a threat. with while_fleeing set it only kicks in once the swimmer has
+/// seen a shark and started fleeing
+pub struct Flee {
+    pub name: &'static str,
+    pub who: Who,
+    pub threat: Target,
+    pub while_fleeing: bool,
+    pub max_turn: f32,
+    pub priority: u8,
+}
+
+impl SteeringBehavior for Flee {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn steer(&self, agent: &Swimmer, neighbors: &[&Swimmer], env: &Environment) -> Option<Desire> {
+        if !self.who.matches(agent) || (self.while_fleeing && !agent.fleeing) {
+            return None;
+        }
+        let (threat, _) = env.resolve(self.threat, agent, neighbors)?;
+        let turn = agent
+            .position
+            .steer_away(threat, agent.rotation, self.max_turn);
+        Some(Desire::heading(agent.rotation + turn))
+    }
+}
+
+/// like seek, but asks to slow down inside slowing_distance so the swimmer doesn't overshoot. only
+/// reynolds kinematics can actually slow down
+pub struct Arrive {
+    pub name: &'static str,
+    pub who: Who,
+    pub target: Target,
+    pub slowing_distance: f32,
+    pub priority: u8,
+}
+
+impl SteeringBehavior for Arrive {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn steer(&self, agent: &Swimmer, neighbors: &[&Swimmer], env: &Environment) -> Option<Desire> {
+        if !self.who.matches(agent) || agent.fleeing {
+            return None;
+        }
+        let (target, _) = env.resolve(self.target, agent, neighbors)?;
+        let distance = agent.position.distance(target);
+        Some(Desire {
+            speed: (distance / self.slowing_distance).min(1.0),
+            ..Desire::heading(agent.position.point_towards(target))
+        })
+    }
+}
+
+/// seek where a moving target is going to be rather than where it is
+pub struct Pursue {
+    pub name: &'static str,
+    pub who: Who,
+    pub target: Target,
+    pub max_turn: f32,
+    pub priority: u8,
+}
+
+impl SteeringBehavior for Pursue {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn steer(&self, agent: &Swimmer, neighbors: &[&Swimmer], env: &Environment) -> Option<Desire> {
+        if !self.who.matches(agent) || agent.fleeing {
+            return None;
+        }
+        let (target, velocity) = env.resolve(self.target, agent, neighbors)?;
+        let turn = agent.position.steer_towards(
+            predict(agent, target, velocity),
+            agent.rotation,
+            self.max_turn,
+        );
+        Some(Desire::heading(agent.rotation + turn))
+    }
+}
+
+/// flee from where a moving threat is going to be rather than where it is
+pub struct Evade {
+    pub name: &'static str,
+    pub who: Who,
+    pub threat: Target,
+    pub max_turn: f32,
+    pub priority: u8,
+}
+
+impl SteeringBehavior for Evade {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn steer(&self, agent: &Swimmer, neighbors: &[&Swimmer], env: &Environment) -> Option<Desire> {
+        if !self.who.matches(agent) {
+            return None;
+        }
+        let (threat, velocity) = env.resolve(self.threat, agent, neighbors)?;
+        let turn = agent.position.steer_away(
+            predict(agent, threat, velocity),
+            agent.rotation,
+            self.max_turn,
+        );
+        Some(Desire::heading(agent.rotation + turn))
+    }
+}
@@ -0,0 +1,158 @@
+use crate::behaviors::{Desire, Environment, SteeringBehavior, Swimmer, Who};
+use crate::components::{Position, Rotation, Vision};
+use crate::constants::{WALL_AVOIDANCE, WALL_PRIORITY};
+use crate::resources::{Obstacle, TankShape};
+use crate::utils::{distance_to_circle_wall, distance_to_obstacle, distance_to_walls};
+use std::f32::consts::PI;
+
+/// turn away from the tank wall when it's within sight straight ahead
+pub struct AvoidWalls {
+    pub who: Who,
+    pub priority: u8,
+}
+
+impl Default for AvoidWalls {
+    fn default() -> Self {
+        AvoidWalls {
+            who: Who::All,
+            priority: WALL_PRIORITY,
+        }
+    }
+}
+
+impl SteeringBehavior for AvoidWalls {
+    fn name(&self) -> &'static str {
+        "walls"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn steer(&self, agent: &Swimmer, _: &[&Swimmer], env: &Environment) -> Option<Desire> {
+        if !self.who.matches(agent) {
+            return None;
+        }
+        let (p, r, v) = (agent.position, agent.rotation, agent.vision);
+        let turn = match env.tank.shape {
+            TankShape::Circle { radius } => circle_wall_turn(p, r, v, radius),
+            TankShape::Rectangle { bounds } => square_wall_turn(p, r, v, bounds),
+        };
+        (turn != 0.0).then(|| Desire::heading(r + Rotation::new(turn)))
+    }
+}
+
+/// steer around obstacles the same way as the circular wall: probe either side and turn towards
+/// whichever has more room
+pub struct AvoidObstacles {
+    pub who: Who,
+    pub priority: u8,
+}
+
+impl Default for AvoidObstacles {
+    fn default() -> Self {
+        AvoidObstacles {
+            who: Who::All,
+            priority: WALL_PRIORITY,
+        }
+    }
+}
+
+impl SteeringBehavior for AvoidObstacles {
+    fn name(&self) -> &'static str {
+        "obstacles"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    // brains can't see obstacles, only the tank wall
+    fn with_brain(&self) -> bool {
+        true
+    }
+
+    fn steer(&self, agent: &Swimmer, _: &[&Swimmer], env: &Environment) -> Option<Desire> {
+        if !self.who.matches(agent) || env.tank.obstacles.is_empty() {
+            return None;
+        }
+        let r = agent.rotation;
+        let turn = obstacle_turn(agent.position, r, agent.vision, &env.tank.obstacles);
+        (turn != 0.0).then(|| Desire::heading(r + Rotation::new(turn)))
+    }
+}
+
+// turns towards whichever side has more room when something is within sight straight ahead
+fn probe_turn(distance: impl Fn(Rotation) -> f32, r: Rotation, v: Vision) -> f32 {
+    if distance(r) >= v.distance {
+        return 0.0;
+    }
+    // a swimmer pressed right up against something would otherwise divide by zero
+    let left = distance(r + Rotation::new(WALL_AVOIDANCE)).max(1.0);
+    let right = distance(r + Rotation::new(-WALL_AVOIDANCE)).max(1.0);
+    if left > right {
+        WALL_AVOIDANCE * (v.distance / left).max(2.0)
+    } else {
+        -WALL_AVOIDANCE * (v.distance / right).max(2.0)
+    }
+}
+
+pub fn obstacle_turn(p: Position, r: Rotation, v: Vision, obstacles: &[Obstacle]) -> f32 {
+    let nearest = |r: Rotation| {
+        obstacles
+            .iter()
+            .map(|o| distance_to_obstacle(p, r, *o))
+            .fold(f32::INFINITY, f32::min)
+    };
+    probe_turn(nearest, r, v)
+}
+
+pub fn circle_wall_turn(p: Position, r: Rotation, v: Vision, radius: f32) -> f32 {
+    probe_turn(|r| distance_to_circle_wall(p, r, radius), r, v)
+}
+
+pub fn square_wall_turn(p: Position, r: Rotation, v: Vision, bounds: [f32; 4]) -> f32 {
+    let (left, right, top, bottom) = distance_to_walls(p, r, bounds);
+    let mut left_turn = 0.0;
+    let mut right_turn = 0.0;
+    if left != 0.0 && left < v.distance {
+        let power = WALL_AVOIDANCE * (v.distance / left).max(2.0);
+        if r.0 > 0.0 {
+            right_turn += power;
+        } else {
+            left_turn += power;
+        }
+    }
+    if right != 0.0 && right < v.distance {
+        let power = WALL_AVOIDANCE * (v.distance / right).max(2.0);
+        if r.0 > 0.0 {
+            left_turn += power;
+        } else {
+            right_turn += power;
+        }
+    }
+    if top != 0.0 && top < v.distance {
+        let power = WALL_AVOIDANCE * (v.distance / top).max(2.0);
+        if r.0 > PI / 2.0 {
+            left_turn += power;
+        } else {
+            right_turn += power;
+        }
+    }
+    if bottom != 0.0 && bottom < v.distance {
+        let power = WALL_AVOIDANCE * (v.distance / bottom).max(2.0);
+        if r.0 < -PI / 2.0 {
+            right_turn += power;
+        } else {
+            left_turn += power;
+        }
+    }
+    if left_turn == right_turn {
+        // bias for right turns (clockwise)
+        -right_turn
+    } else if left_turn > right_turn {
+        left_turn
+    } else {
+        -right_turn
+    }
+}
@@ -0,0 +1,43 @@
+use crate::behaviors::{Desire, Environment, SteeringBehavior, Swimmer, Who};
+use crate::components::Rotation;
+use crate::constants::WANDER_PRIORITY;
+use crate::utils::Direction;
+
+/// a random nudge left or right each tick, as big as the swimmer's noise
+pub struct Wander {
+    pub who: Who,
+    pub priority: u8,
+}
+
+impl Default for Wander {
+    fn default() -> Self {
+        Wander {
+            who: Who::All,
+            priority: WANDER_PRIORITY,
+        }
+    }
+}
+
+impl SteeringBehavior for Wander {
+    fn name(&self) -> &'static str {
+        "wander"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn steer(&self, agent: &Swimmer, _: &[&Swimmer], env: &Environment) -> Option<Desire> {
+        if !self.who.matches(agent) || agent.fleeing {
+            return None;
+        }
+        let (r, n) = (agent.rotation, agent.noise);
+        Some(Desire::heading(
+            match Direction::next(&mut **env.rng.borrow_mut()) {
+                Direction::Left => r + Rotation::new(n.0),
+                Direction::Right => r - Rotation::new(n.0),
+                Direction::Straight => r,
+            },
+        ))
+    }
+}
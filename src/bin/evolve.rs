@@ -0,0 +1,82 @@
+//! evolves a neural network brain for one species by running many headless simulations in
+//! parallel, then writes a species file with the best brain in it that the simulator can load:
+//!
+//!     cargo run --release --bin evolve -- --objective survival --out evolved.ron
+//!     cargo run --release -- evolved.ron
+//!
+//! if the species already has a brain in the input file, evolution carries on from it. brains are
+//! evolved with reynolds kinematics so their speed output counts, and the file written asks the
+//! simulator for the same
+
+use fish::components::Brain;
+use fish::constants::BRAIN_HIDDEN;
+use fish::headless::{evaluate, evolve, Objective, Tuning};
+use fish::resources::Kinematics;
+use rand::thread_rng;
+
+const USAGE: &str = "usage: evolve [--objective survival|polarization] [--target 0.8] \
+[--species fish] [--sharks 3] [--hidden 8] [--candidates 16] [--generations 20] \
+[--updates 1800] [--runs 3] [--threads n] [--in assets/species.ron] [--out evolved.ron]";
+
+fn main() {
+    let options = Tuning::new(Objective::Survival, 20, "evolved.ron")
+        .parse(std::env::args().skip(1), &["--hidden"], USAGE)
+        .and_then(|options| {
+            let hidden = match options.extra.get("--hidden") {
+                Some(v) => v
+                    .parse::<usize>()
+                    .map_err(|e| format!("--hidden: {e}"))?
+                    .max(1),
+                None => BRAIN_HIDDEN,
+            };
+            Ok((options.load()?, hidden, options))
+        });
+    let ((mut base, species), hidden, options) = match options {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+    base.kinematics = Some(Kinematics::Reynolds);
+
+    let population = match &base.species[species].brain {
+        Some(seed) => (0..options.candidates)
+            .map(|i| {
+                if i == 0 {
+                    seed.clone()
+                } else {
+                    Brain::offspring(seed, seed, &mut thread_rng())
+                }
+            })
+            .collect::<Vec<_>>(),
+        None => (0..options.candidates)
+            .map(|_| Brain::random(hidden, &mut thread_rng()))
+            .collect(),
+    };
+    let best = evolve(
+        population,
+        options.generations,
+        options.threads,
+        |brain| {
+            // every fish of the species starts with the same brain
+            let mut config = base.clone();
+            config.species[species].brain = Some(brain.clone());
+            evaluate(&config, options.objective, options.updates, options.runs)
+        },
+        |a, b| Brain::offspring(a, b, &mut thread_rng()),
+        |generation, scored| {
+            let mean = scored.iter().map(|(_, s)| s).sum::<f32>() / scored.len() as f32;
+            println!(
+                "generation {generation}: best {:.4}, mean {mean:.4}",
+                scored[0].1
+            );
+        },
+    );
+
+    base.species[species].brain = Some(best.0);
+    match options.write(&base) {
+        Ok(()) => println!("wrote {} (score {:.4})", options.out, best.1),
+        Err(e) => eprintln!("{e}"),
+    }
+}
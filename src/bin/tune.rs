@@ -0,0 +1,124 @@
+//! evolves a species' schooling parameters by running many headless simulations in parallel, then
+//! writes the best config out as a species file that the simulator can load:
+//!
+//!     cargo run --release --bin tune -- --objective polarization --target 0.8 --out tuned.ron
+//!     cargo run --release -- tuned.ron
+
+use fish::headless::{evaluate, evolve, Objective, Tuning};
+use fish::resources::SpeciesConfig;
+use fish::utils::random_in_range;
+use rand::{random, thread_rng};
+use std::f32::consts::PI;
+
+const USAGE: &str = "usage: tune [--objective polarization|survival] [--target 0.8] \
+[--species fish] [--sharks 3] [--candidates 16] [--generations 10] [--updates 1800] [--runs 3] \
+[--threads n] [--in assets/species.ron] [--out tuned.ron]";
+
+/// the tuned parameters of one species
+#[derive(Clone, Copy, Debug)]
+struct Candidate {
+    separation: f32,
+    alignment: f32,
+    cohesion: f32,
+    noise: f32,
+    vision_distance: f32,
+    vision_angle: f32,
+}
+
+impl Candidate {
+    fn from_config(config: &SpeciesConfig, species: usize) -> Candidate {
+        let s = &config.species[species];
+        Candidate {
+            separation: s.weights.separation,
+            alignment: s.weights.alignment,
+            cohesion: s.weights.cohesion,
+            noise: s.noise,
+            vision_distance: s.vision_distance,
+            vision_angle: s.vision_angle,
+        }
+    }
+
+    fn apply(&self, config: &mut SpeciesConfig, species: usize) {
+        let s = &mut config.species[species];
+        s.weights.separation = self.separation;
+        s.weights.alignment = self.alignment;
+        s.weights.cohesion = self.cohesion;
+        s.noise = self.noise;
+        s.vision_distance = self.vision_distance;
+        s.vision_angle = self.vision_angle;
+    }
+
+    fn genes(&mut self) -> [&mut f32; 6] {
+        [
+            &mut self.separation,
+            &mut self.alignment,
+            &mut self.cohesion,
+            &mut self.noise,
+            &mut self.vision_distance,
+            &mut self.vision_angle,
+        ]
+    }
+
+    // uniform crossover, then each gene has a one in three chance of a nudge of up to 20%
+    fn offspring(a: &Candidate, b: &Candidate) -> Candidate {
+        let (mut child, mut other) = (*a, *b);
+        for (gene, theirs) in child.genes().into_iter().zip(other.genes()) {
+            if random::<bool>() {
+                *gene = *theirs;
+            }
+            if random::<f32>() < 1.0 / 3.0 {
+                *gene = (*gene * random_in_range(0.8, 1.2, &mut thread_rng())).max(0.0);
+            }
+        }
+        child.vision_angle = child.vision_angle.min(PI);
+        child
+    }
+}
+
+fn main() {
+    let options = Tuning::new(Objective::Polarization(0.8), 10, "tuned.ron")
+        .parse(std::env::args().skip(1), &[], USAGE)
+        .and_then(|options| Ok((options.load()?, options)));
+    let ((mut base, species), options) = match options {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+
+    let seed = Candidate::from_config(&base, species);
+    let population = (0..options.candidates)
+        .map(|i| {
+            if i == 0 {
+                seed
+            } else {
+                Candidate::offspring(&seed, &seed)
+            }
+        })
+        .collect::<Vec<_>>();
+    let best = evolve(
+        population,
+        options.generations,
+        options.threads,
+        |c| {
+            let mut config = base.clone();
+            c.apply(&mut config, species);
+            evaluate(&config, options.objective, options.updates, options.runs)
+        },
+        Candidate::offspring,
+        |generation, scored| {
+            let mean = scored.iter().map(|(_, s)| s).sum::<f32>() / scored.len() as f32;
+            println!(
+                "generation {generation}: best {:.4}, mean {mean:.4}, {:?}",
+                scored[0].1, scored[0].0
+            );
+        },
+    );
+
+    best.0.apply(&mut base, species);
+    match options.write(&base) {
+        Ok(()) => println!("wrote {} (score {:.4})", options.out, best.1),
+        Err(e) => eprintln!("{e}"),
+    }
+}
@@ -1,17 +1,53 @@
+mod alarm;
+mod body;
+mod brain;
+mod camera_rig;
+mod coloring;
 mod fish;
 mod fleeing;
+mod food;
+mod genome;
+mod hunter;
+mod inspector;
+mod life;
+mod motion;
+mod noise;
 mod position;
 mod rotation;
+mod selected;
 mod shark;
 mod size;
+mod species_id;
 mod speed;
+mod steering;
+mod tank_geometry;
+mod turn_rate;
 mod vision;
+mod weights;
 
+pub use alarm::*;
+pub use body::*;
+pub use brain::*;
+pub use camera_rig::*;
+pub use coloring::*;
 pub use fish::*;
 pub use fleeing::*;
+pub use food::*;
+pub use genome::*;
+pub use hunter::*;
+pub use inspector::*;
+pub use life::*;
+pub use motion::*;
+pub use noise::*;
 pub use position::*;
 pub use rotation::*;
+pub use selected::*;
 pub use shark::*;
 pub use size::*;
+pub use species_id::*;
 pub use speed::*;
+pub use steering::*;
+pub use tank_geometry::*;
+pub use turn_rate::*;
 pub use vision::*;
+pub use weights::*;
@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// ticks until a fish that noticed others fleeing bolts itself, None while calm
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Alarm(pub Option<f32>);
@@ -0,0 +1,13 @@
+use crate::constants::BODY;
+use bevy::math::Vec2;
+use bevy::prelude::Component;
+
+/// outline of a swimmer at size 1, pointing along +x
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Body(pub [Vec2; 3]);
+
+impl Default for Body {
+    fn default() -> Self {
+        Body(BODY)
+    }
+}
@@ -0,0 +1,113 @@
+use crate::constants::{BRAIN_MUTATION, MUTATION_RATE};
+use crate::utils::{random_in_range, random_normal};
+use bevy::prelude::Component;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// a small feed-forward network that steers a swimmer instead of the hand-tuned rules. senses go
+/// in, one hidden layer, and a turn and a speed come out, all squashed into [-1, 1]. the weights
+/// are stored layer by layer, each neuron's inputs followed by its bias
+#[derive(Component, Clone, Debug, Deserialize, Serialize)]
+pub struct Brain {
+    pub hidden: usize,
+    pub weights: Vec<f32>,
+}
+
+impl Brain {
+    pub const INPUTS: usize = 14;
+    pub const OUTPUTS: usize = 2;
+
+    pub fn size(hidden: usize) -> usize {
+        (Brain::INPUTS + 1) * hidden + (hidden + 1) * Brain::OUTPUTS
+    }
+
+    pub fn random(hidden: usize, rng: &mut impl Rng) -> Brain {
+        Brain {
+            hidden,
+            weights: (0..Brain::size(hidden))
+                .map(|_| random_in_range(-1.0, 1.0, rng))
+                .collect(),
+        }
+    }
+
+    pub fn think(&self, senses: &[f32; Brain::INPUTS]) -> [f32; Brain::OUTPUTS] {
+        let (first, second) = self.weights.split_at((Brain::INPUTS + 1) * self.hidden);
+        let hidden = first
+            .chunks(Brain::INPUTS + 1)
+            .map(|w| neuron(w, senses))
+            .collect::<Vec<_>>();
+        let mut out = [0.0; Brain::OUTPUTS];
+        for (o, w) in out.iter_mut().zip(second.chunks(self.hidden + 1)) {
+            *o = neuron(w, &hidden);
+        }
+        out
+    }
+
+    /// each weight comes from one parent or the other, then may mutate. brains of different shapes
+    /// can't be crossed, the child gets a copy of the first parent's
+    pub fn offspring(a: &Brain, b: &Brain, rng: &mut impl Rng) -> Brain {
+        let mut child = a.clone();
+        if a.hidden == b.hidden {
+            for (w, theirs) in child.weights.iter_mut().zip(&b.weights) {
+                if rng.gen::<bool>() {
+                    *w = *theirs;
+                }
+            }
+        }
+        child.mutate(rng);
+        child
+    }
+
+    pub fn mutate(&mut self, rng: &mut impl Rng) {
+        for w in &mut self.weights {
+            if rng.gen::<f32>() < MUTATION_RATE {
+                *w += random_normal(rng) * BRAIN_MUTATION;
+            }
+        }
+    }
+}
+
+// one neuron: the weighted inputs plus the bias on the end
+fn neuron(weights: &[f32], inputs: &[f32]) -> f32 {
+    let (bias, weights) = weights.split_last().unwrap();
+    let sum: f32 = weights.iter().zip(inputs).map(|(w, x)| w * x).sum();
+    (sum + bias).tanh()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn size_counts_weights_and_biases() {
+        assert_eq!(Brain::size(1), 15 + 2 * 2);
+        assert_eq!(Brain::size(8), 15 * 8 + 9 * 2);
+        let brain = Brain::random(8, &mut StdRng::seed_from_u64(1));
+        assert_eq!(brain.weights.len(), Brain::size(8));
+    }
+
+    #[test]
+    fn think() {
+        let senses = [0.5; Brain::INPUTS];
+        let blank = Brain {
+            hidden: 4,
+            weights: vec![0.0; Brain::size(4)],
+        };
+        assert_eq!(blank.think(&senses), [0.0, 0.0]);
+
+        // one hidden neuron that only listens to its bias, passed on whole to the turn and
+        // negated to the speed
+        let mut weights = vec![0.0; Brain::size(1)];
+        weights[Brain::INPUTS] = 1.0;
+        weights[Brain::INPUTS + 1] = 1.0;
+        weights[Brain::INPUTS + 3] = -1.0;
+        let brain = Brain { hidden: 1, weights };
+        let h = 1f32.tanh();
+        assert_eq!(brain.think(&senses), [h.tanh(), (-h).tanh()]);
+
+        let brain = Brain::random(8, &mut StdRng::seed_from_u64(2));
+        assert!(brain.think(&senses).iter().all(|o| o.abs() <= 1.0));
+    }
+}
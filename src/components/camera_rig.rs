@@ -0,0 +1,25 @@
+use bevy::prelude::Component;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Follow {
+    #[default]
+    Free,
+    Selected,
+    School,
+}
+
+impl Follow {
+    pub fn next(self) -> Follow {
+        match self {
+            Follow::Free => Follow::Selected,
+            Follow::Selected => Follow::School,
+            Follow::School => Follow::Free,
+        }
+    }
+}
+
+/// user controls for the camera it's attached to
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct CameraRig {
+    pub follow: Follow,
+}
@@ -0,0 +1,5 @@
+use bevy::prelude::{Color, Component};
+
+/// the color a swimmer was given at spawn, kept so fish can tell each other apart
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Coloring(pub Color);
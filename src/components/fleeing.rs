@@ -1,13 +1,18 @@
 use bevy::prelude::Component;
 use std::ops::Deref;
 
+/// whether a fish is fleeing. one that bolted because others did keeps going for at least startled
+/// more ticks, shark or no shark
 #[derive(Component, Clone, Copy, Debug, Default)]
-pub struct Fleeing(pub bool);
+pub struct Fleeing {
+    pub active: bool,
+    pub startled: f32,
+}
 
 impl Deref for Fleeing {
     type Target = bool;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.active
     }
 }
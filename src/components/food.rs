@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// a pellet dropped into the tank. it drifts towards the bottom until a fish eats it
+#[derive(Component)]
+pub struct Food;
@@ -0,0 +1,75 @@
+use crate::components::Weights;
+use crate::constants::{FLIGHT_MAX, MUTATION_RATE, MUTATION_SIZE};
+use crate::resources::Species;
+use crate::utils::random_normal;
+use bevy::prelude::Component;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// the heritable part of a swimmer. distances and speed are for size 1, like in Species. a
+/// visible shark only sets a fish fleeing within flee_distance
+#[derive(Component, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct Genome {
+    pub weights: Weights,
+    pub vision_distance: f32,
+    pub vision_angle: f32,
+    pub speed: f32,
+    pub flee_distance: f32,
+    pub generation: u32,
+}
+
+impl Genome {
+    pub fn genes(&self) -> [(&'static str, f32); 7] {
+        [
+            ("separation", self.weights.separation),
+            ("alignment", self.weights.alignment),
+            ("cohesion", self.weights.cohesion),
+            ("vision_distance", self.vision_distance),
+            ("vision_angle", self.vision_angle),
+            ("speed", self.speed),
+            ("flee_distance", self.flee_distance),
+        ]
+    }
+
+    /// each gene comes from one parent or the other, then may mutate
+    pub fn offspring(a: &Genome, b: &Genome, rng: &mut impl Rng) -> Genome {
+        let mut pick = |x: f32, y: f32| {
+            let gene = if rng.gen::<bool>() { x } else { y };
+            mutate(gene, rng)
+        };
+        Genome {
+            weights: Weights {
+                separation: pick(a.weights.separation, b.weights.separation),
+                alignment: pick(a.weights.alignment, b.weights.alignment),
+                cohesion: pick(a.weights.cohesion, b.weights.cohesion),
+            },
+            vision_distance: pick(a.vision_distance, b.vision_distance),
+            vision_angle: pick(a.vision_angle, b.vision_angle).min(PI),
+            speed: pick(a.speed, b.speed),
+            flee_distance: pick(a.flee_distance, b.flee_distance),
+            generation: a.generation.max(b.generation) + 1,
+        }
+    }
+}
+
+// now and then scale a gene by a normally distributed factor. genes never go negative
+fn mutate(gene: f32, rng: &mut impl Rng) -> f32 {
+    if rng.gen::<f32>() >= MUTATION_RATE {
+        return gene;
+    }
+    (gene * (1.0 + random_normal(rng) * MUTATION_SIZE)).max(0.0)
+}
+
+impl From<&Species> for Genome {
+    fn from(s: &Species) -> Self {
+        Genome {
+            weights: s.weights,
+            vision_distance: s.vision_distance,
+            vision_angle: s.vision_angle,
+            speed: s.speed,
+            flee_distance: FLIGHT_MAX,
+            generation: 0,
+        }
+    }
+}
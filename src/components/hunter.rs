@@ -0,0 +1,81 @@
+use crate::components::Speed;
+use crate::constants::{ATTACK_SPEED, REST_SPEED, STALK_SPEED};
+use bevy::prelude::{Component, Entity};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum HuntState {
+    // cruising around looking for prey
+    #[default]
+    Patrol,
+    // prey in sight, closing in slowly
+    Stalk,
+    // a short sprint at the prey that burns energy
+    Attack,
+    // worn out after a chase
+    Rest,
+}
+
+impl HuntState {
+    /// fraction of cruising speed
+    pub fn speed(self) -> f32 {
+        match self {
+            HuntState::Patrol => 1.0,
+            HuntState::Stalk => STALK_SPEED,
+            HuntState::Attack => ATTACK_SPEED,
+            HuntState::Rest => REST_SPEED,
+        }
+    }
+
+    pub fn hunting(self) -> bool {
+        matches!(self, HuntState::Stalk | HuntState::Attack)
+    }
+}
+
+/// what a shark does for its pack
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Role {
+    // hunting alone
+    #[default]
+    Solo,
+    // goes straight for the pack's target
+    Striker,
+    // swings around the far side of the target to drive it towards the striker. angle spreads
+    // several herders out around the target
+    Herder {
+        striker: Entity,
+        angle: f32,
+    },
+}
+
+/// a shark's hunting state. energy runs from 0 to 1, time counts ticks in the current state.
+/// sharks in a pack share a target, solo sharks pick their own
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Hunter {
+    pub state: HuntState,
+    pub energy: f32,
+    pub time: f32,
+    pub cruising: Speed,
+    pub role: Role,
+    pub target: Option<Entity>,
+}
+
+impl Hunter {
+    pub fn new(cruising: Speed) -> Hunter {
+        Hunter {
+            state: HuntState::Patrol,
+            energy: 1.0,
+            time: 0.0,
+            cruising,
+            role: Role::Solo,
+            target: None,
+        }
+    }
+
+    pub fn enter(&mut self, state: HuntState) {
+        if self.state != state {
+            self.state = state;
+            self.time = 0.0;
+        }
+    }
+}
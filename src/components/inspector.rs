@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// the text panel describing the selected swimmer
+#[derive(Component)]
+pub struct Inspector;
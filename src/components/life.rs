@@ -0,0 +1,29 @@
+use crate::utils::random_in_range;
+use bevy::prelude::Component;
+use rand::Rng;
+
+/// how long a swimmer has lived, in ticks, and how much energy it has left. energy runs from 0,
+/// when the swimmer starves, to 1. food eaten past 1 goes into growth. cooldown counts down the
+/// ticks until it can breed again
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Life {
+    pub age: f32,
+    pub energy: f32,
+    pub cooldown: f32,
+}
+
+impl Life {
+    /// newborns start at age 0 with some energy to spare
+    pub fn new(energy: f32) -> Life {
+        Life {
+            age: 0.0,
+            energy,
+            cooldown: 0.0,
+        }
+    }
+
+    // swimmers spawned at startup are staggered so they don't all starve at once
+    pub fn staggered(rng: &mut impl Rng) -> Life {
+        Life::new(random_in_range(0.5, 1.0, rng))
+    }
+}
@@ -0,0 +1,9 @@
+use bevy::math::Vec2;
+use bevy::prelude::Component;
+
+/// velocity for reynolds kinematics. the swimmer's Speed is its max speed
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Motion {
+    pub velocity: Vec2,
+    pub max_force: f32,
+}
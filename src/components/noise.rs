@@ -0,0 +1,14 @@
+use bevy::prelude::Component;
+use std::ops::Deref;
+
+/// how far a swimmer turns when it wanders, in radians
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Noise(pub f32);
+
+impl Deref for Noise {
+    type Target = f32;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -1,9 +1,7 @@
 use crate::components::Rotation;
-use crate::constants::RADIUS;
-use crate::utils::random_in_range;
 use bevy::math::Vec2;
 use bevy::prelude::Component;
-use rand::random;
+use rand::Rng;
 use std::f32::consts::{PI, TAU};
 use std::ops::{Deref, DerefMut};
 
@@ -15,16 +13,17 @@ impl Position {
         Position(Vec2::new(x, y))
     }
 
-    pub fn random_in_square() -> Position {
+    pub fn random_in_square(bounds: [f32; 4], rng: &mut impl Rng) -> Position {
+        let [minx, maxx, miny, maxy] = bounds;
         Position::new(
-            random_in_range(-720.0, 720.0),
-            random_in_range(-360.0, 360.0),
+            minx + rng.gen::<f32>() * (maxx - minx),
+            miny + rng.gen::<f32>() * (maxy - miny),
         )
     }
 
-    pub fn random_in_circle() -> Position {
-        let r = RADIUS * 0.8 * random::<f32>().sqrt();
-        let theta = random::<f32>() * TAU;
+    pub fn random_in_circle(radius: f32, rng: &mut impl Rng) -> Position {
+        let r = radius * 0.8 * rng.gen::<f32>().sqrt();
+        let theta = rng.gen::<f32>() * TAU;
         let x = r * theta.cos();
         let y = r * theta.sin();
         Position::new(x, y)
@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// the swimmer the user is looking at. there is at most one
+#[derive(Component)]
+pub struct Selected;
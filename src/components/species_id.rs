@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// index of a swimmer's species in the SpeciesConfig resource
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SpeciesId(pub usize);
@@ -0,0 +1,150 @@
+use crate::components::Rotation;
+use bevy::math::Vec2;
+use bevy::prelude::Component;
+
+/// one rule's opinion about where a swimmer should be heading this tick
+#[derive(Clone, Copy, Debug)]
+pub struct Contribution {
+    pub rule: &'static str,
+    pub heading: Rotation,
+    // fraction of max speed wanted in that direction. only reynolds kinematics can slow down
+    pub speed: f32,
+    pub weight: f32,
+    pub priority: u8,
+}
+
+/// every rule adds the heading it wants here instead of turning the swimmer itself, so the rules
+/// all see the same heading and their order doesn't matter. steer resolves them once per tick
+#[derive(Component, Clone, Debug, Default)]
+pub struct Steering {
+    pub contributions: Vec<Contribution>,
+    // the heading the rules were resolved against and the turn that was actually made from it,
+    // after priorities and the turn rate
+    pub from: Rotation,
+    pub turn: f32,
+}
+
+impl Steering {
+    pub fn add(&mut self, rule: &'static str, heading: Rotation, weight: f32, priority: u8) {
+        self.add_with_speed(rule, heading, 1.0, weight, priority);
+    }
+
+    pub fn add_with_speed(
+        &mut self,
+        rule: &'static str,
+        heading: Rotation,
+        speed: f32,
+        weight: f32,
+        priority: u8,
+    ) {
+        self.contributions.push(Contribution {
+            rule,
+            heading,
+            speed,
+            weight,
+            priority,
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.contributions.clear();
+        self.turn = 0.0;
+    }
+
+    /// the weighted turn each rule asked for when it was last resolved, in the order the rules
+    /// first added something
+    pub fn wanted(&self) -> Vec<(&'static str, f32)> {
+        let mut wanted: Vec<(&'static str, f32)> = Vec::new();
+        for c in &self.contributions {
+            let turn = c.weight * (c.heading - self.from).0;
+            match wanted.iter_mut().find(|(rule, _)| *rule == c.rule) {
+                Some((_, total)) => *total += turn,
+                None => wanted.push((c.rule, turn)),
+            }
+        }
+        wanted
+    }
+
+    fn priorities(&self) -> Vec<u8> {
+        let mut priorities: Vec<u8> = self.contributions.iter().map(|c| c.priority).collect();
+        priorities.sort_unstable_by(|a, b| b.cmp(a));
+        priorities.dedup();
+        priorities
+    }
+
+    /// works down from the highest priority, letting each level use up as much of the turn rate as
+    /// it needs. anything left over goes to the next level down
+    pub fn resolve(&self, r: Rotation, turn_rate: f32) -> f32 {
+        let mut budget = turn_rate;
+        let mut turn = 0.0;
+        for priority in self.priorities() {
+            let wanted: f32 = self
+                .contributions
+                .iter()
+                .filter(|c| c.priority == priority)
+                .map(|c| c.weight * (c.heading - r).0)
+                .sum();
+            let allowed = wanted.clamp(-budget, budget);
+            turn += allowed;
+            budget -= allowed.abs();
+            if budget <= 0.0 {
+                break;
+            }
+        }
+        turn
+    }
+
+    /// the reynolds version of resolve. each contribution is a desired velocity and pulls the
+    /// current velocity towards it, with the max force shared out by priority the same way
+    pub fn force(&self, velocity: Vec2, max_speed: f32, max_force: f32) -> Vec2 {
+        let mut budget = max_force;
+        let mut force = Vec2::ZERO;
+        for priority in self.priorities() {
+            let wanted: Vec2 = self
+                .contributions
+                .iter()
+                .filter(|c| c.priority == priority)
+                .map(|c| c.weight * (c.heading.unit_vector() * c.speed * max_speed - velocity))
+                .sum();
+            let allowed = wanted.clamp_length_max(budget);
+            force += allowed;
+            budget -= allowed.length();
+            if budget <= 0.0 {
+                break;
+            }
+        }
+        force
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-5
+    }
+
+    #[test]
+    fn higher_priorities_turn_first() {
+        let mut steering = Steering::default();
+        steering.add("low", Rotation::new(0.4), 1.0, 1);
+        steering.add("high", Rotation::new(0.3), 1.0, 2);
+        // the low priority only gets what's left of the turn rate
+        assert!(close(steering.resolve(Rotation::new(0.0), 0.5), 0.5));
+        steering.add("higher", Rotation::new(-0.6), 1.0, 3);
+        // and nothing once a higher one has used it all
+        assert!(close(steering.resolve(Rotation::new(0.0), 0.5), -0.5));
+    }
+
+    #[test]
+    fn same_priority_adds_up_by_weight() {
+        let mut steering = Steering::default();
+        steering.add("a", Rotation::new(0.2), 0.5, 1);
+        steering.add("b", Rotation::new(-0.1), 1.0, 1);
+        assert!(close(steering.resolve(Rotation::new(0.0), 1.0), 0.0));
+        steering.add("c", Rotation::new(0.4), 0.5, 1);
+        assert!(close(steering.resolve(Rotation::new(0.0), 1.0), 0.2));
+        assert!(close(steering.resolve(Rotation::new(0.0), 0.1), 0.1));
+    }
+}
@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// marks the meshes drawn for the tank itself (water and obstacles) so they can be replaced when the
+/// geometry changes
+#[derive(Component)]
+pub struct TankGeometry;
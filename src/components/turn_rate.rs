@@ -0,0 +1,14 @@
+use bevy::prelude::Component;
+use std::ops::Deref;
+
+/// the most a swimmer can turn in one tick, in radians
+#[derive(Component, Clone, Copy, Debug)]
+pub struct TurnRate(pub f32);
+
+impl Deref for TurnRate {
+    type Target = f32;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
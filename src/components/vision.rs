@@ -10,7 +10,7 @@ pub struct Vision {
 }
 
 impl Vision {
-    fn new(distance: f32, angle: f32) -> Vision {
+    pub fn new(distance: f32, angle: f32) -> Vision {
         Vision { distance, angle }
     }
 }
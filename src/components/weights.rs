@@ -0,0 +1,21 @@
+use crate::constants::{ALIGNMENT, COHESION, SEPARATION};
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+/// the most each schooling rule can turn a fish per visible neighbor, in radians
+#[derive(Component, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct Weights {
+    pub separation: f32,
+    pub alignment: f32,
+    pub cohesion: f32,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Weights {
+            separation: SEPARATION,
+            alignment: ALIGNMENT,
+            cohesion: COHESION,
+        }
+    }
+}
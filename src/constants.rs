@@ -1,9 +1,41 @@
+use bevy::math::Vec2;
 use std::f32::consts::PI;
 
 pub const WALL_AVOIDANCE: f32 = PI / 60.0;
 pub const SEPARATION: f32 = PI / 90.0;
 pub const ALIGNMENT: f32 = PI / 180.0;
 pub const COHESION: f32 = PI / 180.0;
+// alignment and cohesion weight each neighbor by how similar it is. fish of another species count
+// for this much, and size and color differences are raised to these powers (0 ignores them)
+pub const OTHER_SPECIES_AFFINITY: f32 = 0.0;
+pub const SIZE_PREFERENCE: f32 = 1.0;
+pub const COLOR_PREFERENCE: f32 = 1.0;
+// steering rules at a higher priority get first claim on a swimmer's turn rate
+pub const WALL_PRIORITY: u8 = 4;
+pub const FLEE_PRIORITY: u8 = 3;
+pub const SEPARATION_PRIORITY: u8 = 2;
+pub const SCHOOLING_PRIORITY: u8 = 1;
+pub const WANDER_PRIORITY: u8 = 0;
+pub const HUNT_PRIORITY: u8 = 2;
+// how far around a fish to count company when sharks look for the most isolated one
+pub const ISOLATION_RADIUS: f32 = 40.0;
+// shark speeds by hunt state, as a fraction of cruising speed
+pub const STALK_SPEED: f32 = 0.6;
+pub const ATTACK_SPEED: f32 = 2.5;
+pub const REST_SPEED: f32 = 0.4;
+// sharks attack once prey is this close, if they have the energy
+pub const ATTACK_DISTANCE: f32 = 80.0;
+pub const ATTACK_MIN_ENERGY: f32 = 0.5;
+// durations are in ticks, energy changes are per tick
+pub const ATTACK_DURATION: f32 = 360.0;
+pub const REST_DURATION: f32 = 600.0;
+pub const ATTACK_DRAIN: f32 = 1.0 / 480.0;
+pub const ENERGY_RECOVERY: f32 = 1.0 / 1200.0;
+// how far past the prey herders go before closing in, and how far apart several herders spread
+pub const HERD_DISTANCE: f32 = 60.0;
+pub const HERD_SPREAD: f32 = PI / 3.0;
+// reynolds steering force at size 1, in speed per tick
+pub const MAX_FORCE: f32 = 0.05;
 pub const TIME_RATE: f32 = 120.0;
 pub const NFISH: usize = 400;
 pub const NSHARKS: usize = 0;
@@ -11,15 +43,111 @@ pub const FISH_SPEED: f32 = 1.25;
 pub const SHARK_SPEED: f32 = 0.75;
 pub const FLIGHT_MAX: f32 = 200.0;
 pub const FLIGHT_SPEED: f32 = 4.0;
+// fleeing fish calm down once every shark is this many times their flee distance away
+pub const FLIGHT_HYSTERESIS: f32 = 1.25;
+// chance per tick that a fish startles for each fleeing fish it can see, and how many ticks it
+// takes to react
+pub const ALARM_SENSITIVITY: f32 = 0.05;
+pub const ALARM_LATENCY: f32 = 10.0;
+// ticks a startled fish keeps fleeing before it can calm down again
+pub const STARTLE_DURATION: f32 = 120.0;
 pub const VISIBLE_DISTANCE: f32 = 75.0;
 pub const VISIBLE_ANGLE: f32 = PI * 3.0 / 4.0;
 pub const FISH_NOISE: f32 = PI / 45.0;
 pub const SHARK_NOISE: f32 = PI / 90.0;
+pub const FISH_TURN_RATE: f32 = PI / 4.0;
+pub const SHARK_TURN_RATE: f32 = PI / 8.0;
+// outline of a swimmer at size 1, pointing along +x. used for both the mesh and collisions
+pub const BODY: [Vec2; 3] = [
+    Vec2::new(10.0, 0.0),
+    Vec2::new(-3.0, 3.0),
+    Vec2::new(-3.0, -3.0),
+];
 pub const FISH_SIZE_RANGE: (f32, f32) = (0.5, 2.0);
 pub const SHARK_SIZE_RANGE: (f32, f32) = (1.5, 6.0);
 pub const WIDTH: f32 = 1280.0;
 pub const HEIGHT: f32 = 720.0;
 pub const BOUNDS: [f32; 4] = [-WIDTH / 2.0, WIDTH / 2.0, -HEIGHT / 2.0, HEIGHT / 2.0];
 pub const RADIUS: f32 = HEIGHT / 2.0;
+pub const FOOD_SIZE: f32 = 1.0;
+pub const FOOD_SINK_SPEED: f32 = 0.1;
+pub const FOOD_DRIFT: f32 = 0.15;
+pub const FOOD_ATTRACTION: f32 = PI / 30.0;
+// lifecycle, in ticks. a pellet is worth FOOD_ENERGY, and energy burns at METABOLISM per tick per
+// unit of size (faster while fleeing). surplus energy grows a fish by GROWTH size per unit
+pub const FOOD_ENERGY: f32 = 0.3;
+pub const METABOLISM: f32 = 1.0 / 72000.0;
+pub const GROWTH: f32 = 0.5;
+pub const MAX_SIZE: f32 = 3.0;
+// chance of dying each tick is MORTALITY, doubling every SENESCENCE ticks of age
+pub const MORTALITY: f32 = 1e-6;
+pub const SENESCENCE: f32 = 6000.0;
+// breeding. adults with enough energy breed with a visible adult of their species, each giving
+// up half of BREEDING_COST to an offspring that starts at OFFSPRING_SIZE of a normal newcomer
+pub const ADULT_AGE: f32 = 3600.0;
+pub const BREEDING_ENERGY: f32 = 0.8;
+pub const BREEDING_COST: f32 = 0.4;
+pub const BREEDING_COOLDOWN: f32 = 2400.0;
+pub const OFFSPRING_SIZE: f32 = 0.4;
+// each gene an offspring inherits has MUTATION_RATE chance of being scaled by a random factor with
+// standard deviation MUTATION_SIZE
+pub const MUTATION_RATE: f32 = 0.1;
+pub const MUTATION_SIZE: f32 = 0.1;
+// neural network brains. BRAIN_HIDDEN neurons between the senses and the two outputs, and the
+// sharpest turn a brain can ask for per tick. each weight an offspring inherits has MUTATION_RATE
+// chance of a normally distributed nudge with standard deviation BRAIN_MUTATION
+pub const BRAIN_HIDDEN: usize = 8;
+pub const BRAIN_TURN: f32 = PI / 30.0;
+pub const BRAIN_MUTATION: f32 = 0.3;
+// the angle either side of straight ahead a brain probes for walls, and how many neighbors count
+// as a crowd
+pub const BRAIN_WALL_PROBE: f32 = PI / 4.0;
+pub const BRAIN_CROWD: f32 = 10.0;
+// gene statistics per species and generation are taken every GENE_SAMPLE ticks. set gene_file in
+// the species file to append them to a csv
+pub const GENE_SAMPLE: f32 = 1200.0;
+// energy a shark gets from a catch, per unit of the fish's size
+pub const PREY_ENERGY: f32 = 0.5;
+// pellets dropped at random per second of simulated time, to keep the fish fed
+pub const FEED_RATE: f32 = 2.0;
+// population counts are taken every POPULATION_SAMPLE ticks and the last POPULATION_HISTORY kept.
+// set population_file in the species file to also append them to a csv
+pub const POPULATION_SAMPLE: f32 = 120.0;
+pub const POPULATION_HISTORY: usize = 600;
+pub const ZOOM_RANGE: (f32, f32) = (0.1, 10.0);
+// fraction of the way to its target the camera moves each second when following
+pub const FOLLOW_RATE: f32 = 5.0;
+// length of the steering overlay lines per radian of turn
+pub const STEERING_SCALE: f32 = 300.0;
+pub const SPATIAL_CELL_SIZE: f32 = VISIBLE_DISTANCE;
+// scent fields. diffusion and decay are fractions per tick, emission is per tick except the alarm,
+// which is released all at once
+pub const SCENT_CELL_SIZE: f32 = 20.0;
+pub const FOOD_SCENT_DIFFUSION: f32 = 0.1;
+pub const FOOD_SCENT_DECAY: f32 = 0.002;
+pub const FOOD_SCENT: f32 = 1.0;
+pub const ALARM_DIFFUSION: f32 = 0.15;
+pub const ALARM_DECAY: f32 = 0.01;
+pub const ALARM_RELEASE: f32 = 200.0;
+pub const SHARK_SCENT_DIFFUSION: f32 = 0.05;
+pub const SHARK_SCENT_DECAY: f32 = 0.005;
+pub const SHARK_SCENT: f32 = 0.5;
+// gradients shallower than this go unnoticed
+pub const SCENT_THRESHOLD: f32 = 0.0005;
+pub const SCENT_AVOIDANCE: f32 = PI / 20.0;
+pub const FLOW_PULSE: f32 = 0.0;
+// how hard fish turn to face into the current
+pub const RHEOTAXIS: f32 = PI / 60.0;
 pub const USE_CIRLCE: bool = true;
+// resize the tank to fill the window, following it when the window is resized
+pub const FOLLOW_WINDOW: bool = true;
+// (x, y, radius)
+pub const OBSTACLES: &[(f32, f32, f32)] = &[];
+pub const SPECIES_FILE: &str = "assets/species.ron";
+// seconds of simulated time per update when running headless
+pub const HEADLESS_STEP: f32 = 1.0 / 60.0;
+// training episodes end after EPISODE_STEPS steps of the environment. an agent that dies loses
+// DEATH_PENALTY on top of whatever energy it had left
+pub const EPISODE_STEPS: usize = 3600;
+pub const DEATH_PENALTY: f32 = 1.0;
 pub const PERF: bool = false;
@@ -0,0 +1,202 @@
+use crate::components::{Brain, Life, SpeciesId};
+use crate::constants::{DEATH_PENALTY, EPISODE_STEPS};
+use crate::headless::headless_app;
+use crate::resources::{Action, Actions, Kinematics, Observations, SimRng, SpeciesConfig};
+use crate::systems::{compute_visibility, index_positions, observe};
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+
+/// what one agent senses, the same inputs a brain gets. see behaviors::senses
+pub type Observation = [f32; Brain::INPUTS];
+
+/// what came of one step. everything lines up with TankEnv::agents
+#[derive(Clone, Debug)]
+pub struct Step {
+    pub observations: Vec<Observation>,
+    pub rewards: Vec<f32>,
+    // an agent is done once it has died. its observations are zeros and its rewards 0 from then on
+    pub dones: Vec<bool>,
+    // the episode is over, every agent is done or max_steps have passed
+    pub finished: bool,
+}
+
+/// the simulation as a step-able environment for training a policy, with no window. every swimmer
+/// of one species is an agent, the rest of the tank runs as usual. agents are steered by the
+/// actions they're given, the same way a brain steers, and rewarded by the change in their energy
+/// each step, so fish earn by eating and sharks by catching. dying costs DEATH_PENALTY. the tank
+/// always runs reynolds kinematics so the speed of an action counts
+pub struct TankEnv {
+    config: SpeciesConfig,
+    species: SpeciesId,
+    pub max_steps: usize,
+    app: App,
+    agents: Vec<Entity>,
+    energy: Vec<f32>,
+    dones: Vec<bool>,
+    steps: usize,
+}
+
+impl TankEnv {
+    /// None if there's no species with that name. call reset before the first step
+    pub fn new(mut config: SpeciesConfig, species: &str) -> Option<TankEnv> {
+        config.kinematics = Some(Kinematics::Reynolds);
+        let species = SpeciesId(config.find(species)?);
+        Some(TankEnv {
+            app: headless_app(config.clone()),
+            config,
+            species,
+            max_steps: EPISODE_STEPS,
+            agents: Vec::new(),
+            energy: Vec::new(),
+            dones: Vec::new(),
+            steps: 0,
+        })
+    }
+
+    /// the agents of the current episode, in the order observations, actions and rewards use.
+    /// swimmers born during an episode aren't agents, they follow the rules
+    pub fn agents(&self) -> &[Entity] {
+        &self.agents
+    }
+
+    /// the whole simulation, for anything the observations leave out
+    pub fn world(&self) -> &World {
+        &self.app.world
+    }
+
+    /// starts a new episode from a fresh tank. the seed fixes everything random in the episode,
+    /// from where every swimmer starts to which fish a shark gets confused by, so the same seed
+    /// and the same actions give the same steps
+    pub fn reset(&mut self, seed: u64) -> Vec<Observation> {
+        self.app = headless_app(self.config.clone());
+        self.app.insert_resource(SimRng::seeded(seed));
+        // the first update runs startup, which spawns everyone
+        self.app.update();
+
+        let mut swimmers = self.app.world.query::<(Entity, &SpeciesId)>();
+        self.agents = swimmers
+            .iter(&self.app.world)
+            .filter(|(_, species)| **species == self.species)
+            .map(|(e, _)| e)
+            .collect();
+        self.agents.sort();
+        self.energy = self.agents.iter().map(|e| self.life(*e).energy).collect();
+        self.dones = vec![false; self.agents.len()];
+        self.steps = 0;
+        self.observe()
+    }
+
+    /// gives every agent its action, in the order of agents, and advances the simulation one
+    /// update. agents without an action this step follow the rules, actions for agents that are
+    /// done are ignored
+    pub fn step(&mut self, actions: &[Action]) -> Step {
+        let mut given = self.app.world.resource_mut::<Actions>();
+        given.0.clear();
+        for ((e, action), done) in self.agents.iter().zip(actions).zip(&self.dones) {
+            if !done {
+                given.0.insert(*e, *action);
+            }
+        }
+        self.app.update();
+        self.steps += 1;
+
+        let mut rewards = vec![0.0; self.agents.len()];
+        for (i, e) in self.agents.iter().enumerate() {
+            if self.dones[i] {
+                continue;
+            }
+            match self.app.world.get::<Life>(*e) {
+                Some(life) => {
+                    rewards[i] = life.energy - self.energy[i];
+                    self.energy[i] = life.energy;
+                }
+                None => {
+                    rewards[i] = -self.energy[i] - DEATH_PENALTY;
+                    self.dones[i] = true;
+                }
+            }
+        }
+        Step {
+            observations: self.observe(),
+            rewards,
+            dones: self.dones.clone(),
+            finished: self.steps >= self.max_steps || self.dones.iter().all(|d| *d),
+        }
+    }
+
+    fn life(&self, e: Entity) -> Life {
+        *self.app.world.get::<Life>(e).unwrap()
+    }
+
+    // visibility is worked out at the start of each update, so it's redone here for the tank as
+    // it is now
+    fn observe(&mut self) -> Vec<Observation> {
+        self.app.world.run_system_once(index_positions);
+        self.app.world.run_system_once(compute_visibility);
+        self.app.world.run_system_once(observe);
+        let observations = self.app.world.resource::<Observations>();
+        self.agents
+            .iter()
+            .map(|e| observations.0.get(e).copied().unwrap_or_default())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Motion;
+    use crate::headless::set_predators;
+
+    fn episode(env: &mut TankEnv, seed: u64) -> Vec<Step> {
+        env.reset(seed);
+        let actions = vec![
+            Action {
+                turn: 0.5,
+                speed: 0.5
+            };
+            env.agents().len()
+        ];
+        (0..120).map(|_| env.step(&actions)).collect()
+    }
+
+    // the mean speed of the agents after some steps of the same action
+    fn mean_speed(env: &mut TankEnv, speed: f32) -> f32 {
+        env.reset(3);
+        let actions = vec![Action { turn: 0.0, speed }; env.agents().len()];
+        for _ in 0..60 {
+            env.step(&actions);
+        }
+        let agents = env.agents().to_vec();
+        let speeds: Vec<f32> = agents
+            .iter()
+            .filter_map(|e| env.world().get::<Motion>(*e))
+            .map(|motion| motion.velocity.length())
+            .collect();
+        speeds.iter().sum::<f32>() / speeds.len() as f32
+    }
+
+    #[test]
+    fn same_seed_same_steps() {
+        let mut config = SpeciesConfig::default();
+        set_predators(&mut config, 2);
+        let mut env = TankEnv::new(config, "fish").unwrap();
+        let first = episode(&mut env, 7);
+        let second = episode(&mut env, 7);
+        assert!(!env.agents().is_empty());
+        for (a, b) in first.iter().zip(&second) {
+            assert_eq!(a.observations, b.observations);
+            assert_eq!(a.rewards, b.rewards);
+            assert_eq!(a.dones, b.dones);
+        }
+    }
+
+    #[test]
+    fn speed_actions_change_velocity() {
+        // the default species file swims with heading kinematics, which would ignore speed
+        let mut env = TankEnv::new(SpeciesConfig::default(), "fish").unwrap();
+        let slow = mean_speed(&mut env, 0.0);
+        let fast = mean_speed(&mut env, 1.0);
+        assert!(fast > slow * 1.5, "slow {slow}, fast {fast}");
+    }
+}
@@ -0,0 +1,341 @@
+use crate::components::{IsFish, Rotation};
+use crate::constants::{HEADLESS_STEP, SPECIES_FILE};
+use crate::resources::SpeciesConfig;
+use crate::SimulationPlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::utils::HashMap;
+use rand::random;
+use std::thread;
+use std::time::Duration;
+
+/// an app that runs the simulation with no window or renderer. every update advances time by
+/// HEADLESS_STEP seconds however long it really took, so runs are as fast as the cpu allows
+pub fn headless_app(config: SpeciesConfig) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        // swimmers are still spawned with meshes, nothing draws them
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            HEADLESS_STEP,
+        )))
+        .insert_resource(config)
+        .add_plugins(SimulationPlugin);
+    app.finish();
+    app.cleanup();
+    app
+}
+
+/// what a tuning run is trying to get out of the simulation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Objective {
+    // how close the school's average polarization gets to the target
+    Polarization(f32),
+    // the fraction of fish still alive at the end, with sharks in the tank
+    Survival,
+}
+
+/// scores a config by the mean of several headless runs of the given number of updates, higher is
+/// better. one run is too noisy to tell candidates apart
+pub fn evaluate(config: &SpeciesConfig, objective: Objective, updates: usize, runs: usize) -> f32 {
+    let runs = runs.max(1);
+    (0..runs)
+        .map(|_| run(config.clone(), objective, updates))
+        .sum::<f32>()
+        / runs as f32
+}
+
+fn run(config: SpeciesConfig, objective: Objective, updates: usize) -> f32 {
+    let mut app = headless_app(config);
+    app.update();
+    let initial = count_fish(&mut app.world);
+    let mut total = 0.0;
+    let mut samples = 0;
+    for update in 0..updates {
+        app.update();
+        // let the school settle before measuring
+        if update >= updates / 2 {
+            total += polarization(&mut app.world);
+            samples += 1;
+        }
+    }
+    match objective {
+        Objective::Polarization(target) => -(total / samples.max(1) as f32 - target).abs(),
+        Objective::Survival => count_fish(&mut app.world) as f32 / initial.max(1) as f32,
+    }
+}
+
+/// scores every candidate on its own thread pool of the given size, in the same order
+pub fn evaluate_all<C: Sync>(
+    candidates: &[C],
+    threads: usize,
+    score: impl Fn(&C) -> f32 + Sync,
+) -> Vec<f32> {
+    let chunk = candidates.len().div_ceil(threads.max(1)).max(1);
+    let score = &score;
+    thread::scope(|scope| {
+        let handles = candidates
+            .chunks(chunk)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(score).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("simulation panicked"))
+            .collect()
+    })
+}
+
+/// the better of two random candidates
+pub fn tournament<C>(scored: &[(C, f32)]) -> &C {
+    let pick = || &scored[(random::<f32>() * scored.len() as f32) as usize % scored.len()];
+    let (a, b) = (pick(), pick());
+    if a.1 >= b.1 {
+        &a.0
+    } else {
+        &b.0
+    }
+}
+
+// how many of the best carry on to the next generation as they are
+const ELITES: usize = 2;
+
+/// a genetic algorithm. every generation each candidate is scored, the best ELITES carry on as
+/// they are and the rest are bred from tournaments. report gets each generation's scores, best
+/// first. one lucky run can put a newcomer on top, so what comes back is the best of the last
+/// generation's elites, which have been scored every generation since they were bred
+pub fn evolve<C: Clone + Sync>(
+    mut population: Vec<C>,
+    generations: usize,
+    threads: usize,
+    score: impl Fn(&C) -> f32 + Sync,
+    breed: impl Fn(&C, &C) -> C,
+    mut report: impl FnMut(usize, &[(C, f32)]),
+) -> (C, f32) {
+    let candidates = population.len();
+    let mut best = (population[0].clone(), f32::NEG_INFINITY);
+    for generation in 0..generations {
+        let scores = evaluate_all(&population, threads, &score);
+        let mut scored = population.into_iter().zip(scores).collect::<Vec<_>>();
+        // after the first generation the elites lead the population
+        let elites = if generation == 0 {
+            scored.len()
+        } else {
+            ELITES
+        };
+        if let Some(top) = scored
+            .iter()
+            .take(elites)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+        {
+            best = top.clone();
+        }
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        report(generation, &scored);
+        population = scored.iter().take(ELITES).map(|(c, _)| c.clone()).collect();
+        while population.len() < candidates {
+            population.push(breed(tournament(&scored), tournament(&scored)));
+        }
+    }
+    best
+}
+
+/// sets how many of every predator species to spawn
+pub fn set_predators(config: &mut SpeciesConfig, count: usize) {
+    let SpeciesConfig {
+        species,
+        population,
+        ..
+    } = config;
+    for p in population {
+        if species.iter().any(|s| s.name == p.species && s.predator) {
+            p.count = count;
+        }
+    }
+}
+
+pub fn count_fish(world: &mut World) -> usize {
+    world.query_filtered::<(), IsFish>().iter(world).count()
+}
+
+/// the length of the mean heading. 1 when every fish swims the same way, near 0 when they're random
+pub fn polarization(world: &mut World) -> f32 {
+    let mut query = world.query_filtered::<&Rotation, IsFish>();
+    let (sum, n) = query.iter(world).fold((Vec2::ZERO, 0), |(sum, n), r| {
+        (sum + r.unit_vector(), n + 1)
+    });
+    if n == 0 {
+        0.0
+    } else {
+        sum.length() / n as f32
+    }
+}
+
+/// the command line options tune and evolve share. each tool starts from its own defaults
+#[derive(Clone, Debug)]
+pub struct Tuning {
+    pub objective: Objective,
+    pub species: String,
+    pub sharks: usize,
+    pub candidates: usize,
+    pub generations: usize,
+    pub updates: usize,
+    pub runs: usize,
+    pub threads: usize,
+    pub input: String,
+    pub out: String,
+    // the values of flags only one tool knows, keyed by flag
+    pub extra: HashMap<String, String>,
+}
+
+impl Tuning {
+    pub fn new(objective: Objective, generations: usize, out: &str) -> Self {
+        Tuning {
+            objective,
+            species: "fish".to_string(),
+            sharks: 3,
+            candidates: 16,
+            generations,
+            updates: 1800,
+            runs: 3,
+            threads: thread::available_parallelism().map_or(4, |n| n.get()),
+            input: SPECIES_FILE.to_string(),
+            out: out.to_string(),
+            extra: HashMap::new(),
+        }
+    }
+
+    /// reads the flags in args over the defaults. the flags in extra are kept as they are for the
+    /// tool to make sense of, anything else unknown gets the usage back
+    pub fn parse(
+        mut self,
+        args: impl IntoIterator<Item = String>,
+        extra: &[&str],
+        usage: &str,
+    ) -> Result<Self, String> {
+        let (mut target, mut survival) = match self.objective {
+            Objective::Polarization(target) => (target, false),
+            Objective::Survival => (0.8, true),
+        };
+        let mut args = args.into_iter();
+        while let Some(flag) = args.next() {
+            let mut value = || args.next().ok_or(format!("{flag} needs a value"));
+            let number = |v: String| v.parse::<f32>().map_err(|e| format!("{flag}: {e}"));
+            let count = |v: String| v.parse::<usize>().map_err(|e| format!("{flag}: {e}"));
+            match flag.as_str() {
+                "--objective" => match value()?.as_str() {
+                    "polarization" => survival = false,
+                    "survival" => survival = true,
+                    other => return Err(format!("unknown objective {other}")),
+                },
+                "--target" => target = number(value()?)?,
+                "--species" => self.species = value()?,
+                "--sharks" => self.sharks = count(value()?)?,
+                "--candidates" => self.candidates = count(value()?)?.max(2),
+                "--generations" => self.generations = count(value()?)?,
+                "--updates" => self.updates = count(value()?)?,
+                "--runs" => self.runs = count(value()?)?.max(1),
+                "--threads" => self.threads = count(value()?)?.max(1),
+                "--in" => self.input = value()?,
+                "--out" => self.out = value()?,
+                f if extra.contains(&f) => {
+                    let v = value()?;
+                    self.extra.insert(flag, v);
+                }
+                _ => return Err(usage.to_string()),
+            }
+        }
+        self.objective = if survival {
+            Objective::Survival
+        } else {
+            Objective::Polarization(target)
+        };
+        Ok(self)
+    }
+
+    /// the input species file and where the species being tuned is in it. survival needs
+    /// something to survive, so it gets the sharks asked for
+    pub fn load(&self) -> Result<(SpeciesConfig, usize), String> {
+        let mut config = SpeciesConfig::load_or_default(&self.input);
+        let species = config.find(&self.species).ok_or(format!(
+            "no species named {:?} in {}",
+            self.species, self.input
+        ))?;
+        if self.objective == Objective::Survival {
+            set_predators(&mut config, self.sharks);
+        }
+        Ok((config, species))
+    }
+
+    /// writes the tuned config out as a species file
+    pub fn write(&self, config: &SpeciesConfig) -> Result<(), String> {
+        let pretty = ron::ser::PrettyConfig::default();
+        let ron = ron::ser::to_string_pretty(config, pretty)
+            .map_err(|e| format!("couldn't serialize the config: {e}"))?;
+        std::fs::write(&self.out, ron).map_err(|e| format!("couldn't write {}: {e}", self.out))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::schedule::{LogLevel, ScheduleBuildSettings, ScheduleLabel};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Mutex;
+
+    #[test]
+    fn evolve_keeps_the_best() {
+        let (best, score) = evolve(
+            vec![1.0, 5.0, 3.0, 2.0],
+            3,
+            2,
+            |c| *c,
+            |a, b| (a + b) / 2.0,
+            |_, _| {},
+        );
+        assert_eq!((best, score), (5.0, 5.0));
+    }
+
+    #[test]
+    fn evolve_returns_a_rescored_elite() {
+        // newcomers get one lucky run, after that they score what they're worth
+        let next = AtomicU32::new(100);
+        let seen = Mutex::new(Vec::new());
+        let score = |c: &(u32, f32)| {
+            let mut seen = seen.lock().unwrap();
+            if c.0 >= 100 && !seen.contains(&c.0) {
+                seen.push(c.0);
+                return 1000.0;
+            }
+            c.1
+        };
+        let breed =
+            |a: &(u32, f32), _: &(u32, f32)| (next.fetch_add(1, Ordering::Relaxed), a.1 - 1.0);
+        let (best, score) = evolve(
+            vec![(0, 1.0), (1, 2.0), (2, 3.0)],
+            4,
+            1,
+            score,
+            breed,
+            |_, _| {},
+        );
+        assert!(score < 1000.0);
+        assert_eq!(score, best.1);
+    }
+
+    #[test]
+    fn no_ambiguous_systems() {
+        let mut app = headless_app(SpeciesConfig::default());
+        for label in [Startup.intern(), Update.intern()] {
+            app.edit_schedule(label, |schedule| {
+                schedule.set_build_settings(ScheduleBuildSettings {
+                    ambiguity_detection: LogLevel::Error,
+                    ..default()
+                });
+            });
+        }
+        // building a schedule with ambiguities panics
+        app.update();
+        app.update();
+    }
+}
@@ -0,0 +1,214 @@
+pub mod behaviors;
+pub mod components;
+pub mod constants;
+pub mod env;
+pub mod headless;
+pub mod resources;
+pub mod systems;
+pub mod utils;
+
+use crate::behaviors::*;
+use crate::constants::{
+    FLEE_PRIORITY, FOLLOW_WINDOW, FOOD_ATTRACTION, SCENT_AVOIDANCE, SCHOOLING_PRIORITY,
+    SPECIES_FILE, WANDER_PRIORITY,
+};
+use crate::resources::*;
+use crate::systems::*;
+use bevy::prelude::*;
+use std::f32::consts::PI;
+
+/// the simulation itself, with no window, input or drawing, so it can also run headless. more
+/// behaviors can be added with add_steering_behavior after this plugin. a SpeciesConfig inserted
+/// before this plugin is used instead of the species file
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<SpeciesConfig>() {
+            app.insert_resource(SpeciesConfig::load_or_default(SPECIES_FILE));
+        }
+        if let Some(kinematics) = app.world.resource::<SpeciesConfig>().kinematics {
+            app.insert_resource(kinematics);
+        }
+        if let Some(confusion) = app.world.resource::<SpeciesConfig>().confusion {
+            app.insert_resource(confusion);
+        }
+        if let Some(pattern) = app.world.resource::<SpeciesConfig>().flow {
+            app.insert_resource(Flow {
+                pattern,
+                ..default()
+            });
+        }
+        app.init_resource::<Tank>()
+            .init_resource::<SpatialIndex>()
+            .init_resource::<VisibilityMap>()
+            .init_resource::<Kinematics>()
+            .init_resource::<Confusion>()
+            .init_resource::<PreySelection>()
+            .init_resource::<HuntStats>()
+            .init_resource::<Scents>()
+            .init_resource::<Flow>()
+            .init_resource::<SpeciesMeshes>()
+            .init_resource::<PopulationHistory>()
+            .init_resource::<GeneStats>()
+            .init_resource::<Actions>()
+            .init_resource::<Observations>()
+            .init_resource::<SimRng>()
+            .init_resource::<SteeringBehaviors>()
+            .add_steering_behavior(Flee {
+                name: "flee",
+                who: Who::Fish,
+                threat: Target::NearestPredator(f32::INFINITY),
+                while_fleeing: true,
+                max_turn: PI,
+                priority: FLEE_PRIORITY,
+            })
+            .add_steering_behavior(Separation::default())
+            .add_steering_behavior(Alignment::default())
+            .add_steering_behavior(Cohesion::default())
+            .add_steering_behavior(Seek {
+                name: "food",
+                who: Who::Fish,
+                target: Target::NearestFood,
+                max_turn: FOOD_ATTRACTION,
+                priority: SCHOOLING_PRIORITY,
+            })
+            .add_steering_behavior(FollowScent {
+                name: "food scent",
+                who: Who::Fish,
+                chemical: Chemical::Food,
+                avoid: false,
+                max_turn: FOOD_ATTRACTION,
+                priority: WANDER_PRIORITY,
+            })
+            .add_steering_behavior(FollowScent {
+                name: "alarm scent",
+                who: Who::Fish,
+                chemical: Chemical::Alarm,
+                avoid: true,
+                max_turn: SCENT_AVOIDANCE,
+                priority: SCHOOLING_PRIORITY,
+            })
+            .add_steering_behavior(FollowScent {
+                name: "shark scent",
+                who: Who::Fish,
+                chemical: Chemical::Shark,
+                avoid: true,
+                max_turn: SCENT_AVOIDANCE,
+                priority: SCHOOLING_PRIORITY,
+            })
+            .add_steering_behavior(Rheotaxis::default())
+            .add_steering_behavior(Hunt::default())
+            .add_steering_behavior(Wander::default())
+            .add_steering_behavior(AvoidWalls::default())
+            .add_steering_behavior(AvoidObstacles::default())
+            .add_steering_behavior(Think::default())
+            .add_steering_behavior(Act::default())
+            .add_systems(Startup, population_startup)
+            .add_systems(
+                Update,
+                (
+                    // anything a moved wall left outside is back in before anyone looks
+                    keep_in_tank,
+                    (
+                        // who sees whom first, then calm fish so they can be startled again
+                        // straight away, then alarms count down and go off
+                        (
+                            compute_visibility,
+                            stop_fleeing,
+                            spread_alarm,
+                            start_fleeing,
+                        )
+                            .chain(),
+                        clear_steering,
+                        diffuse_scents,
+                        advance_flow,
+                    ),
+                    update_hunters,
+                    coordinate_packs,
+                    pick_prey,
+                    apply_behaviors,
+                    // only one of these runs, chained so the schedule doesn't have to know that
+                    (
+                        steer.run_if(heading_kinematics),
+                        accelerate.run_if(reynolds_kinematics),
+                    )
+                        .chain(),
+                    movement,
+                    index_positions,
+                    collide,
+                    strike,
+                    eat_food,
+                    live,
+                    breed,
+                    translate,
+                    rotate,
+                )
+                    .chain(),
+            )
+            // after behaviors, which see the food, and in a fixed place among the other systems
+            // that draw random numbers so a seeded run plays out the same every time
+            .add_systems(
+                Update,
+                (feed, drift_food)
+                    .chain()
+                    .after(apply_behaviors)
+                    .before(movement),
+            )
+            .add_systems(Update, (record_population, record_genes).after(eat_food));
+    }
+}
+
+/// the simulation in a window, with the camera, mouse and keyboard controls, inspector and
+/// overlays. needs DefaultPlugins
+pub struct FishPlugin;
+
+impl Plugin for FishPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(SimulationPlugin)
+            .insert_resource(ClearColor(Color::rgb(0.05, 0.05, 0.05)))
+            .init_resource::<Overlays>()
+            .add_systems(Startup, (camera_startup, inspector_startup))
+            .add_systems(
+                Startup,
+                fit_tank_startup
+                    .run_if(|| FOLLOW_WINDOW)
+                    .before(population_startup),
+            )
+            .add_systems(
+                Update,
+                fit_tank_to_window
+                    .run_if(|| FOLLOW_WINDOW)
+                    .before(keep_in_tank),
+            )
+            .add_systems(Update, (render_tank, outline_tank))
+            .add_systems(
+                Update,
+                (pan_camera, zoom_camera, camera_keys, follow_camera)
+                    .chain()
+                    .after(rotate),
+            )
+            .add_systems(Update, (pick_swimmer, drop_food).chain().before(feed))
+            .add_systems(
+                Update,
+                (update_inspector, highlight_selected).after(eat_food),
+            )
+            .add_systems(
+                Update,
+                (
+                    toggle_overlays,
+                    draw_vision,
+                    draw_neighbors,
+                    draw_probes,
+                    draw_steering,
+                    draw_flee_radius,
+                    draw_grid,
+                    draw_scents,
+                    draw_flow,
+                    draw_population,
+                )
+                    .after(eat_food),
+            )
+            .add_systems(Update, bevy::window::close_on_esc);
+    }
+}
@@ -1,15 +1,10 @@
-mod components;
-mod constants;
-mod systems;
-mod utils;
-
-use crate::constants::{PERF, USE_CIRLCE};
 use bevy::prelude::*;
+use fish::constants::PERF;
+use fish::resources::SpeciesConfig;
+use fish::systems::perf_startup;
+use fish::FishPlugin;
 use iyes_perf_ui::PerfUiPlugin;
 
-use crate::systems::*;
-use crate::utils::*;
-
 struct Perf;
 
 impl Plugin for Perf {
@@ -25,12 +20,7 @@ impl Plugin for Perf {
 
 fn main() {
     // TODO:
-    //       add a resource with a kdtree or other spatial partitioning for more efficient querying
-    //       add a visibility resource calculated from the kdtree
-    //       split sac system once they can each access the visibility resource
     //       make more things proportionate to size (e.g. vision) this allows larger numbers in the same size tank without density problems
-    //       be more deliberate with creating different kinds of fish
-    //       filter clustering behavior based on fish of similar size/color
     //       give fish a turn rate based on size (big turns slow)
     //       make small fish afraid of large fish. remove the distinction between fish and sharks
     //       have a more progressive form of fleeing, avoid large fish like a wall and only flee if they get really close. give flight a duration instead of a distance
@@ -42,27 +32,11 @@ fn main() {
     if PERF {
         app.add_plugins(Perf);
     }
+    // a species file given on the command line, e.g. one written by the tuner, replaces the default
+    if let Some(path) = std::env::args().nth(1) {
+        app.insert_resource(SpeciesConfig::load_or_default(&path));
+    }
     app.add_plugins(DefaultPlugins)
-        .add_systems(Startup, (fish_startup, sharks_startup))
-        .add_systems(
-            Update,
-            (
-                start_fleeing,
-                stop_fleeing,
-                sac,
-                fish_wander,
-                sharks_wander,
-                if USE_CIRLCE {
-                    avoid_circle_walls
-                } else {
-                    avoid_square_walls
-                },
-                movement,
-                translate,
-                rotate,
-            )
-                .chain(),
-        )
-        .add_systems(Update, bevy::window::close_on_esc)
+        .add_plugins(FishPlugin)
         .run()
 }
@@ -0,0 +1,27 @@
+mod confusion;
+mod flow;
+mod gene_stats;
+mod kinematics;
+mod overlays;
+mod policy;
+mod population;
+mod scents;
+mod sim_rng;
+mod spatial_index;
+mod species;
+mod tank;
+mod visibility_map;
+
+pub use confusion::*;
+pub use flow::*;
+pub use gene_stats::*;
+pub use kinematics::*;
+pub use overlays::*;
+pub use policy::*;
+pub use population::*;
+pub use scents::*;
+pub use sim_rng::*;
+pub use spatial_index::*;
+pub use species::*;
+pub use tank::*;
+pub use visibility_map::*;
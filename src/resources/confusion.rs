@@ -0,0 +1,42 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+/// how confused a shark gets by the number of fish it can see, from 0 (picks and catches its
+/// target every time) to 1 (can't focus on anything)
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub enum Confusion {
+    None,
+    // rises by rate for every fish past the first, up to max
+    Linear { rate: f32, max: f32 },
+    // rises quickly then levels off towards max, reaching half of max at half fish past the first
+    Saturating { half: f32, max: f32 },
+}
+
+impl Confusion {
+    pub fn of(self, visible: usize) -> f32 {
+        let extra = visible.saturating_sub(1) as f32;
+        match self {
+            Confusion::None => 0.0,
+            Confusion::Linear { rate, max } => (rate * extra).min(max),
+            Confusion::Saturating { half, max } => max * extra / (extra + half),
+        }
+        .clamp(0.0, 1.0)
+    }
+}
+
+impl Default for Confusion {
+    // how much crowds of fish throw sharks off in a new tank
+    fn default() -> Self {
+        Confusion::Saturating {
+            half: 10.0,
+            max: 0.9,
+        }
+    }
+}
+
+/// every strike sharks have made and how many of them caught a fish
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct HuntStats {
+    pub strikes: u32,
+    pub catches: u32,
+}
@@ -0,0 +1,93 @@
+use crate::constants::FLOW_PULSE;
+use crate::utils::perlin;
+use bevy::math::Vec2;
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+
+/// the shape of the water's movement. speeds are in the same units as swimmer speeds. a new tank
+/// starts still unless the species file says otherwise
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub enum FlowPattern {
+    #[default]
+    Still,
+    // the same everywhere, like a river
+    Uniform {
+        velocity: Vec2,
+    },
+    // circling a point, like a pump. fastest at radius, slowing towards the center and beyond it
+    Vortex {
+        center: Vec2,
+        speed: f32,
+        radius: f32,
+    },
+    // eddies that wander over time. scale is the size of an eddy, change is how far the pattern
+    // moves through the noise per tick
+    Noise {
+        speed: f32,
+        scale: f32,
+        change: f32,
+    },
+}
+
+impl FlowPattern {
+    pub fn at(self, p: Vec2, time: f32) -> Vec2 {
+        match self {
+            FlowPattern::Still => Vec2::ZERO,
+            FlowPattern::Uniform { velocity } => velocity,
+            FlowPattern::Vortex {
+                center,
+                speed,
+                radius,
+            } => {
+                let offset = p - center;
+                let d = offset.length() / radius;
+                // a rankine-like profile: spins up linearly to radius then falls off
+                let falloff = if d < 1.0 { d } else { 1.0 / d };
+                offset.perp().normalize_or_zero() * speed * falloff
+            }
+            FlowPattern::Noise {
+                speed,
+                scale,
+                change,
+            } => {
+                let angle = perlin(p / scale + Vec2::new(0.0, time * change)) * TAU;
+                Vec2::from_angle(angle) * speed
+            }
+        }
+    }
+}
+
+/// the water current. pulse is the period in ticks of a surge that swings the current between half
+/// and full strength, 0 to keep it steady
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct Flow {
+    pub pattern: FlowPattern,
+    pub pulse: f32,
+    pub time: f32,
+}
+
+impl Flow {
+    pub fn at(&self, p: Vec2) -> Vec2 {
+        let strength = if self.pulse > 0.0 {
+            0.75 + 0.25 * (self.time / self.pulse * TAU).sin()
+        } else {
+            1.0
+        };
+        self.pattern.at(p, self.time) * strength
+    }
+
+    pub fn is_still(&self) -> bool {
+        self.pattern == FlowPattern::Still
+    }
+}
+
+impl Default for Flow {
+    fn default() -> Self {
+        Flow {
+            pattern: FlowPattern::default(),
+            pulse: FLOW_PULSE,
+            time: 0.0,
+        }
+    }
+}
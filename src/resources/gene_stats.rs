@@ -0,0 +1,21 @@
+use bevy::prelude::Resource;
+
+/// mean and standard deviation of every gene among the living swimmers of one species and
+/// generation
+#[derive(Clone, Debug)]
+pub struct GenerationStats {
+    pub species: usize,
+    pub generation: u32,
+    pub count: usize,
+    pub genes: Vec<(&'static str, f32, f32)>,
+}
+
+/// the latest gene statistics. elapsed counts ticks since startup, since ticks since the last
+/// sample, and samples how many have been taken
+#[derive(Resource, Clone, Debug, Default)]
+pub struct GeneStats {
+    pub elapsed: f32,
+    pub since: f32,
+    pub samples: usize,
+    pub generations: Vec<GenerationStats>,
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::{Res, Resource};
+use serde::{Deserialize, Serialize};
+
+/// how steering turns into movement. the default is the one the simulation starts with
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Kinematics {
+    // swimmers always move at their speed, steering only turns them (within their turn rate)
+    #[default]
+    Heading,
+    // classic reynolds boids. steering is a force on a velocity, limited by a max force and max
+    // speed, so swimmers can slow down and speed up
+    Reynolds,
+}
+
+pub fn heading_kinematics(kinematics: Res<Kinematics>) -> bool {
+    *kinematics == Kinematics::Heading
+}
+
+pub fn reynolds_kinematics(kinematics: Res<Kinematics>) -> bool {
+    *kinematics == Kinematics::Reynolds
+}
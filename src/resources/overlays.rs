@@ -0,0 +1,15 @@
+use bevy::prelude::Resource;
+
+/// which debug gizmo layers are drawn. each is toggled by a number key
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct Overlays {
+    pub vision: bool,
+    pub neighbors: bool,
+    pub probes: bool,
+    pub steering: bool,
+    pub flee: bool,
+    pub grid: bool,
+    pub scent: bool,
+    pub flow: bool,
+    pub population: bool,
+}
@@ -0,0 +1,20 @@
+use crate::components::Brain;
+use bevy::prelude::{Entity, Resource};
+use bevy::utils::HashMap;
+
+/// what an outside policy wants one swimmer to do this tick. turn runs from -1 to 1 as a fraction
+/// of the sharpest turn a brain could make, speed from 0 to 1 as a fraction of max speed
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Action {
+    pub turn: f32,
+    pub speed: f32,
+}
+
+/// swimmers steered from outside the simulation, e.g. by a policy being trained. they skip the
+/// rules like swimmers with a brain do. cleared by whoever sets it, not by the simulation
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Actions(pub HashMap<Entity, Action>);
+
+/// what every swimmer senses, the same inputs a brain gets. only filled in by observe
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Observations(pub HashMap<Entity, [f32; Brain::INPUTS]>);
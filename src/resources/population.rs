@@ -0,0 +1,15 @@
+use bevy::prelude::Resource;
+use bevy::sprite::Mesh2dHandle;
+use std::collections::VecDeque;
+
+/// the mesh for each species, by SpeciesId, so swimmers born later share them
+#[derive(Resource, Clone, Debug, Default)]
+pub struct SpeciesMeshes(pub Vec<Mesh2dHandle>);
+
+/// how many of each species were alive at each sample, oldest first. since counts ticks since the
+/// last sample
+#[derive(Resource, Clone, Debug, Default)]
+pub struct PopulationHistory {
+    pub since: f32,
+    pub samples: VecDeque<Vec<usize>>,
+}
@@ -0,0 +1,221 @@
+use crate::constants::{
+    ALARM_DECAY, ALARM_DIFFUSION, FOOD_SCENT_DECAY, FOOD_SCENT_DIFFUSION, SCENT_CELL_SIZE,
+    SHARK_SCENT_DECAY, SHARK_SCENT_DIFFUSION,
+};
+use bevy::math::Vec2;
+use bevy::prelude::Resource;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Chemical {
+    // given off by food pellets
+    Food,
+    // released by fish when a shark catches one
+    Alarm,
+    // trails behind sharks
+    Shark,
+}
+
+/// a concentration on a uniform grid over the tank. every tick it spreads to neighboring cells
+/// and fades away. diffusion is the fraction exchanged with each neighbor per tick, decay the
+/// fraction lost per tick
+#[derive(Clone, Debug)]
+pub struct Field {
+    pub cell_size: f32,
+    pub diffusion: f32,
+    pub decay: f32,
+    origin: Vec2,
+    width: usize,
+    height: usize,
+    values: Vec<f32>,
+    scratch: Vec<f32>,
+}
+
+impl Field {
+    pub fn new(cell_size: f32, diffusion: f32, decay: f32) -> Field {
+        Field {
+            cell_size,
+            diffusion,
+            decay,
+            origin: Vec2::ZERO,
+            width: 0,
+            height: 0,
+            values: Vec::new(),
+            scratch: Vec::new(),
+        }
+    }
+
+    /// cover new bounds. whatever was in the field is lost
+    pub fn fit(&mut self, bounds: [f32; 4]) {
+        let [minx, maxx, miny, maxy] = bounds;
+        let width = ((maxx - minx) / self.cell_size).ceil().max(1.0) as usize;
+        let height = ((maxy - miny) / self.cell_size).ceil().max(1.0) as usize;
+        let origin = Vec2::new(minx, miny);
+        if (width, height, origin) == (self.width, self.height, self.origin) {
+            return;
+        }
+        self.origin = origin;
+        self.width = width;
+        self.height = height;
+        self.values = vec![0.0; width * height];
+        self.scratch = vec![0.0; width * height];
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    fn cell(&self, p: Vec2) -> Option<(usize, usize)> {
+        let c = ((p - self.origin) / self.cell_size).floor();
+        let (x, y) = (c.x as i64, c.y as i64);
+        let inside = x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height;
+        inside.then_some((x as usize, y as usize))
+    }
+
+    // clamps to the edge so the field has no gradient off the grid
+    fn get(&self, x: i64, y: i64) -> f32 {
+        if self.values.is_empty() {
+            return 0.0;
+        }
+        let x = x.clamp(0, self.width as i64 - 1) as usize;
+        let y = y.clamp(0, self.height as i64 - 1) as usize;
+        self.values[self.index(x, y)]
+    }
+
+    pub fn emit(&mut self, p: Vec2, amount: f32) {
+        if let Some((x, y)) = self.cell(p) {
+            let i = self.index(x, y);
+            self.values[i] += amount;
+        }
+    }
+
+    /// bilinear between cell centers
+    pub fn sample(&self, p: Vec2) -> f32 {
+        let c = (p - self.origin) / self.cell_size - 0.5;
+        let (x, y) = (c.x.floor() as i64, c.y.floor() as i64);
+        let (fx, fy) = (c.x - c.x.floor(), c.y - c.y.floor());
+        let bottom = self.get(x, y) * (1.0 - fx) + self.get(x + 1, y) * fx;
+        let top = self.get(x, y + 1) * (1.0 - fx) + self.get(x + 1, y + 1) * fx;
+        bottom * (1.0 - fy) + top * fy
+    }
+
+    /// which way and how steeply the concentration rises at p, per unit distance
+    pub fn gradient(&self, p: Vec2) -> Vec2 {
+        let h = self.cell_size;
+        let dx = self.sample(p + Vec2::X * h) - self.sample(p - Vec2::X * h);
+        let dy = self.sample(p + Vec2::Y * h) - self.sample(p - Vec2::Y * h);
+        Vec2::new(dx, dy) / (2.0 * h)
+    }
+
+    pub fn step(&mut self, ticks: f32) {
+        // more than a quarter per neighbor per step is unstable
+        let rate = (self.diffusion * ticks).min(0.25);
+        let keep = (1.0 - self.decay).powf(ticks);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let (xi, yi) = (x as i64, y as i64);
+                let here = self.get(xi, yi);
+                let around = self.get(xi - 1, yi)
+                    + self.get(xi + 1, yi)
+                    + self.get(xi, yi - 1)
+                    + self.get(xi, yi + 1);
+                let i = self.index(x, y);
+                self.scratch[i] = (here + rate * (around - 4.0 * here)) * keep;
+            }
+        }
+        std::mem::swap(&mut self.values, &mut self.scratch);
+    }
+
+    /// the center and concentration of every cell above min
+    pub fn cells(&self, min: f32) -> impl Iterator<Item = (Vec2, f32)> + '_ {
+        (0..self.height).flat_map(move |y| {
+            (0..self.width).filter_map(move |x| {
+                let value = self.values[self.index(x, y)];
+                let center = self.origin + (Vec2::new(x as f32, y as f32) + 0.5) * self.cell_size;
+                (value > min).then_some((center, value))
+            })
+        })
+    }
+}
+
+/// every chemical in the water
+#[derive(Resource, Clone, Debug)]
+pub struct Scents {
+    pub food: Field,
+    pub alarm: Field,
+    pub shark: Field,
+}
+
+impl Scents {
+    pub fn field(&self, chemical: Chemical) -> &Field {
+        match chemical {
+            Chemical::Food => &self.food,
+            Chemical::Alarm => &self.alarm,
+            Chemical::Shark => &self.shark,
+        }
+    }
+
+    pub fn fields_mut(&mut self) -> [&mut Field; 3] {
+        [&mut self.food, &mut self.alarm, &mut self.shark]
+    }
+}
+
+impl Default for Scents {
+    fn default() -> Self {
+        Scents {
+            food: Field::new(SCENT_CELL_SIZE, FOOD_SCENT_DIFFUSION, FOOD_SCENT_DECAY),
+            alarm: Field::new(SCENT_CELL_SIZE, ALARM_DIFFUSION, ALARM_DECAY),
+            shark: Field::new(SCENT_CELL_SIZE, SHARK_SCENT_DIFFUSION, SHARK_SCENT_DECAY),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total(field: &Field) -> f32 {
+        field.cells(f32::NEG_INFINITY).map(|(_, v)| v).sum()
+    }
+
+    #[test]
+    fn diffusion_without_decay_conserves_mass() {
+        let mut field = Field::new(10.0, 0.2, 0.0);
+        field.fit([0.0, 100.0, 0.0, 100.0]);
+        field.emit(Vec2::new(5.0, 5.0), 1.0);
+        field.emit(Vec2::new(50.0, 50.0), 2.0);
+        for _ in 0..200 {
+            field.step(1.0);
+        }
+        assert!((total(&field) - 3.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn gradient_points_to_the_peak() {
+        let mut field = Field::new(10.0, 0.2, 0.01);
+        field.fit([0.0, 200.0, 0.0, 200.0]);
+        let peak = Vec2::new(100.0, 100.0);
+        field.emit(peak, 1.0);
+        for _ in 0..20 {
+            field.step(1.0);
+        }
+        for p in [
+            Vec2::new(70.0, 100.0),
+            Vec2::new(100.0, 140.0),
+            Vec2::new(75.0, 120.0),
+        ] {
+            assert!(field.gradient(p).dot(peak - p) > 0.0);
+        }
+    }
+
+    #[test]
+    fn fit_to_degenerate_bounds() {
+        let mut field = Field::new(10.0, 0.2, 0.01);
+        for bounds in [[5.0, 5.0, 5.0, 5.0], [10.0, -10.0, 10.0, -10.0]] {
+            field.fit(bounds);
+            field.emit(Vec2::new(5.0, 5.0), 1.0);
+            field.step(1.0);
+            field.sample(Vec2::new(5.0, 5.0));
+            field.gradient(Vec2::ZERO);
+        }
+    }
+}
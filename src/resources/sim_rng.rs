@@ -0,0 +1,20 @@
+use bevy::prelude::Resource;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// where the simulation gets its random numbers. seeded from the os unless it's given a seed, and
+/// then the same seed and the same inputs play out the same way
+#[derive(Resource)]
+pub struct SimRng(pub StdRng);
+
+impl SimRng {
+    pub fn seeded(seed: u64) -> SimRng {
+        SimRng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for SimRng {
+    fn default() -> Self {
+        SimRng(StdRng::from_entropy())
+    }
+}
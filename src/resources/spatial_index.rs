@@ -0,0 +1,60 @@
+use crate::constants::SPATIAL_CELL_SIZE;
+use bevy::math::Vec2;
+use bevy::prelude::{Entity, Resource};
+use bevy::utils::HashMap;
+
+/// a uniform grid over the tank, rebuilt every tick, for finding what is near a point without
+/// comparing every pair of swimmers
+#[derive(Resource, Debug)]
+pub struct SpatialIndex {
+    pub cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<(Entity, Vec2)>>,
+}
+
+impl SpatialIndex {
+    pub fn new(cell_size: f32) -> SpatialIndex {
+        SpatialIndex {
+            cell_size,
+            cells: HashMap::default(),
+        }
+    }
+
+    pub fn cell(&self, p: Vec2) -> (i32, i32) {
+        (
+            (p.x / self.cell_size).floor() as i32,
+            (p.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    pub fn insert(&mut self, e: Entity, p: Vec2) {
+        let cell = self.cell(p);
+        self.cells.entry(cell).or_default().push((e, p));
+    }
+
+    /// the cells that have anything in them
+    pub fn occupied(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// everything within radius of p. may include a few entities that are slightly further away,
+    /// callers that care should check the distance themselves
+    pub fn nearby(&self, p: Vec2, radius: f32) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let (minx, miny) = self.cell(p - Vec2::splat(radius));
+        let (maxx, maxy) = self.cell(p + Vec2::splat(radius));
+        (minx..=maxx)
+            .flat_map(move |x| (miny..=maxy).map(move |y| (x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+    }
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        SpatialIndex::new(SPATIAL_CELL_SIZE)
+    }
+}
@@ -0,0 +1,310 @@
+use crate::components::{Body, Brain, Weights};
+use crate::constants::*;
+use crate::resources::{Confusion, FlowPattern, Kinematics};
+use crate::utils::{random_in_range, random_normal};
+use bevy::math::Vec2;
+use bevy::prelude::{warn, Color, Resource};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::f32::consts::PI;
+use std::fs;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum SizeDistribution {
+    Uniform {
+        min: f32,
+        max: f32,
+    },
+    // clamped to [min, max] so the tails can't produce microscopic or enormous fish
+    Normal {
+        mean: f32,
+        deviation: f32,
+        min: f32,
+        max: f32,
+    },
+}
+
+impl SizeDistribution {
+    pub fn sample(self, rng: &mut impl Rng) -> f32 {
+        match self {
+            SizeDistribution::Uniform { min, max } => random_in_range(min, max, rng),
+            SizeDistribution::Normal {
+                mean,
+                deviation,
+                min,
+                max,
+            } => (mean + random_normal(rng) * deviation).clamp(min, max),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum Palette {
+    // ranges of hue (in degrees), saturation and lightness
+    Hsl {
+        hue: (f32, f32),
+        saturation: (f32, f32),
+        lightness: (f32, f32),
+    },
+    // one of these rgb colors
+    Rgb(Vec<(f32, f32, f32)>),
+}
+
+impl Palette {
+    pub fn sample(&self, rng: &mut impl Rng) -> Color {
+        match self {
+            Palette::Hsl {
+                hue,
+                saturation,
+                lightness,
+            } => Color::hsl(
+                random_in_range(hue.0, hue.1, rng),
+                random_in_range(saturation.0, saturation.1, rng),
+                random_in_range(lightness.0, lightness.1, rng),
+            ),
+            Palette::Rgb(colors) => {
+                let (r, g, b) = colors[rng.gen_range(0..colors.len())];
+                Color::rgb(r, g, b)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum Shape {
+    // the original slim triangle
+    Dart,
+    // short and broad
+    Wedge,
+    // long and thin
+    Needle,
+    // nose first, pointing along +x, at size 1
+    Custom([(f32, f32); 3]),
+}
+
+impl Shape {
+    pub fn body(self) -> Body {
+        match self {
+            Shape::Dart => Body::default(),
+            Shape::Wedge => Body([
+                Vec2::new(7.0, 0.0),
+                Vec2::new(-4.0, 5.0),
+                Vec2::new(-4.0, -5.0),
+            ]),
+            Shape::Needle => Body([
+                Vec2::new(14.0, 0.0),
+                Vec2::new(-4.0, 2.0),
+                Vec2::new(-4.0, -2.0),
+            ]),
+            Shape::Custom(vertices) => Body(vertices.map(|(x, y)| Vec2::new(x, y))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Species {
+    pub name: String,
+    // predators are spawned as sharks, everything else as fish
+    pub predator: bool,
+    pub size: SizeDistribution,
+    // speed and vision distance are given for size 1 and scaled by each swimmer's size
+    pub speed: f32,
+    pub vision_distance: f32,
+    pub vision_angle: f32,
+    pub turn_rate: f32,
+    // only used by reynolds kinematics. scaled by size like speed
+    #[serde(default = "default_max_force")]
+    pub max_force: f32,
+    pub noise: f32,
+    pub weights: Weights,
+    pub palette: Palette,
+    pub shape: Shape,
+    // swimmers of a species with a brain steer by it instead of by the rules, see Think
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brain: Option<Brain>,
+}
+
+impl Species {
+    fn validate(&self) -> Result<(), String> {
+        let (min, max) = match self.size {
+            SizeDistribution::Uniform { min, max } => (min, max),
+            SizeDistribution::Normal { min, max, .. } => (min, max),
+        };
+        if min > max {
+            return Err(format!("size range {min} to {max} is backwards"));
+        }
+        // everything scales with size, and a swimmer of size 0 could be seen from anywhere
+        if min <= 0.0 {
+            return Err(format!("sizes have to be above 0, got a min of {min}"));
+        }
+        if let Palette::Rgb(colors) = &self.palette {
+            if colors.is_empty() {
+                return Err("palette has no colors".to_string());
+            }
+        }
+        for (name, value) in [
+            ("speed", self.speed),
+            ("vision_distance", self.vision_distance),
+            ("vision_angle", self.vision_angle),
+            ("turn_rate", self.turn_rate),
+            ("noise", self.noise),
+        ] {
+            if value < 0.0 {
+                return Err(format!("{name} can't be negative, got {value}"));
+            }
+        }
+        // it's measured either side of the heading
+        if self.vision_angle > PI {
+            return Err(format!(
+                "vision_angle can't be more than PI, got {}",
+                self.vision_angle
+            ));
+        }
+        // written by hand, or for a brain with a different number of senses
+        if let Some(brain) = &self.brain {
+            let wanted = Brain::size(brain.hidden);
+            if brain.weights.len() != wanted {
+                return Err(format!(
+                    "brain with {} hidden neurons needs {wanted} weights, got {}",
+                    brain.hidden,
+                    brain.weights.len()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn default_max_force() -> f32 {
+    MAX_FORCE
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Population {
+    pub species: String,
+    pub count: usize,
+}
+
+/// every species that can live in the tank and how many of each to spawn
+#[derive(Resource, Clone, Debug, Deserialize, Serialize)]
+pub struct SpeciesConfig {
+    pub species: Vec<Species>,
+    pub population: Vec<Population>,
+    // overrides the default kinematics, e.g. for brains that were evolved to use their speed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kinematics: Option<Kinematics>,
+    // overrides how much crowds of fish throw sharks off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confusion: Option<Confusion>,
+    // sets the water current going
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flow: Option<FlowPattern>,
+    // a csv to append the population counts to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub population_file: Option<String>,
+    // a csv to append the gene statistics to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gene_file: Option<String>,
+}
+
+impl SpeciesConfig {
+    pub fn load(path: &str) -> Result<SpeciesConfig, Box<dyn Error>> {
+        let config: SpeciesConfig = ron::from_str(&fs::read_to_string(path)?)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// catches the mistakes that would otherwise only show up as a panic or nonsense at spawn
+    pub fn validate(&self) -> Result<(), String> {
+        for p in &self.population {
+            if self.find(&p.species).is_none() {
+                return Err(format!(
+                    "population refers to unknown species {:?}",
+                    p.species
+                ));
+            }
+        }
+        for s in &self.species {
+            s.validate()
+                .map_err(|e| format!("species {:?}: {e}", s.name))?;
+        }
+        Ok(())
+    }
+
+    /// falls back to the built in fish and sharks if the file can't be read
+    pub fn load_or_default(path: &str) -> SpeciesConfig {
+        SpeciesConfig::load(path).unwrap_or_else(|e| {
+            warn!("couldn't load species from {path}, using defaults: {e}");
+            SpeciesConfig::default()
+        })
+    }
+
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.species.iter().position(|s| s.name == name)
+    }
+}
+
+impl Default for SpeciesConfig {
+    fn default() -> Self {
+        SpeciesConfig {
+            species: vec![
+                Species {
+                    name: "fish".into(),
+                    predator: false,
+                    size: SizeDistribution::Uniform {
+                        min: FISH_SIZE_RANGE.0,
+                        max: FISH_SIZE_RANGE.1,
+                    },
+                    speed: FISH_SPEED,
+                    vision_distance: VISIBLE_DISTANCE,
+                    vision_angle: VISIBLE_ANGLE,
+                    turn_rate: FISH_TURN_RATE,
+                    max_force: MAX_FORCE,
+                    noise: FISH_NOISE,
+                    weights: Weights::default(),
+                    palette: Palette::Hsl {
+                        hue: (180.0, 250.0),
+                        saturation: (0.3, 0.7),
+                        lightness: (0.3, 0.7),
+                    },
+                    shape: Shape::Dart,
+                    brain: None,
+                },
+                Species {
+                    name: "shark".into(),
+                    predator: true,
+                    size: SizeDistribution::Uniform {
+                        min: SHARK_SIZE_RANGE.0,
+                        max: SHARK_SIZE_RANGE.1,
+                    },
+                    speed: SHARK_SPEED,
+                    vision_distance: VISIBLE_DISTANCE,
+                    vision_angle: PI / 3.0,
+                    turn_rate: SHARK_TURN_RATE,
+                    max_force: MAX_FORCE,
+                    noise: SHARK_NOISE,
+                    weights: Weights::default(),
+                    palette: Palette::Rgb(vec![(0.75, 0.75, 0.75)]),
+                    shape: Shape::Dart,
+                    brain: None,
+                },
+            ],
+            population: vec![
+                Population {
+                    species: "fish".into(),
+                    count: NFISH,
+                },
+                Population {
+                    species: "shark".into(),
+                    count: NSHARKS,
+                },
+            ],
+            kinematics: None,
+            confusion: None,
+            flow: None,
+            population_file: None,
+            gene_file: None,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::components::Position;
+use crate::constants::{BOUNDS, OBSTACLES, RADIUS, USE_CIRLCE};
+use bevy::math::Vec2;
+use bevy::prelude::Resource;
+use rand::Rng;
+
+#[derive(Clone, Copy, Debug)]
+pub enum TankShape {
+    Circle { radius: f32 },
+    // [minx, maxx, miny, maxy]
+    Rectangle { bounds: [f32; 4] },
+}
+
+/// a round rock or pillar inside the tank
+#[derive(Clone, Copy, Debug)]
+pub struct Obstacle {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+impl Obstacle {
+    pub fn new(x: f32, y: f32, radius: f32) -> Obstacle {
+        Obstacle {
+            center: Vec2::new(x, y),
+            radius,
+        }
+    }
+}
+
+/// the active tank geometry. walls, obstacles and the rendered tank are all driven from this, so
+/// changing it at runtime changes both what the fish avoid and what is drawn
+#[derive(Resource, Clone, Debug)]
+pub struct Tank {
+    pub shape: TankShape,
+    pub obstacles: Vec<Obstacle>,
+}
+
+impl Default for Tank {
+    fn default() -> Self {
+        Tank {
+            shape: if USE_CIRLCE {
+                TankShape::Circle { radius: RADIUS }
+            } else {
+                TankShape::Rectangle { bounds: BOUNDS }
+            },
+            obstacles: OBSTACLES
+                .iter()
+                .map(|&(x, y, radius)| Obstacle::new(x, y, radius))
+                .collect(),
+        }
+    }
+}
+
+impl Tank {
+    /// resize the walls to fill a window of the given size, keeping the current shape
+    pub fn fit(&mut self, width: f32, height: f32) {
+        // a minimized window has no size, the tank keeps its last one
+        if width <= 0.0 || height <= 0.0 {
+            return;
+        }
+        self.shape = match self.shape {
+            TankShape::Circle { .. } => TankShape::Circle {
+                radius: width.min(height) / 2.0,
+            },
+            TankShape::Rectangle { .. } => TankShape::Rectangle {
+                bounds: [-width / 2.0, width / 2.0, -height / 2.0, height / 2.0],
+            },
+        };
+    }
+
+    /// the smallest rectangle containing the tank, as [minx, maxx, miny, maxy]
+    pub fn bounds(&self) -> [f32; 4] {
+        match self.shape {
+            TankShape::Circle { radius } => [-radius, radius, -radius, radius],
+            TankShape::Rectangle { bounds } => bounds,
+        }
+    }
+
+    pub fn random_position(&self, rng: &mut impl Rng) -> Position {
+        match self.shape {
+            TankShape::Circle { radius } => Position::random_in_circle(radius, rng),
+            TankShape::Rectangle { bounds } => Position::random_in_square(bounds, rng),
+        }
+    }
+
+    /// pulls a position that is outside the walls back inside, leaving a margin so it isn't
+    /// immediately pinned against the wall
+    pub fn contain(&self, p: Position, margin: f32) -> Position {
+        match self.shape {
+            TankShape::Circle { radius } => {
+                let limit = (radius - margin).max(0.0);
+                if p.length() > limit {
+                    Position(p.normalize_or_zero() * limit)
+                } else {
+                    p
+                }
+            }
+            TankShape::Rectangle {
+                bounds: [minx, maxx, miny, maxy],
+            } => Position::new(
+                p.x.clamp(minx + margin, (maxx - margin).max(minx + margin)),
+                p.y.clamp(miny + margin, (maxy - margin).max(miny + margin)),
+            ),
+        }
+    }
+}
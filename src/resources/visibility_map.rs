@@ -0,0 +1,21 @@
+use bevy::prelude::{Entity, Resource};
+use bevy::utils::HashMap;
+use std::ops::{Deref, DerefMut};
+
+/// which swimmers each (non-fleeing) swimmer can see this tick
+#[derive(Resource, Debug, Default)]
+pub struct VisibilityMap(pub HashMap<Entity, Vec<Entity>>);
+
+impl Deref for VisibilityMap {
+    type Target = HashMap<Entity, Vec<Entity>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for VisibilityMap {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
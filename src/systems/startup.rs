@@ -3,73 +3,119 @@ use std::f32::consts::PI;
 use bevy::prelude::*;
 use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
 use iyes_perf_ui::PerfUiCompleteBundle;
+use rand::Rng;
 
 use crate::components::*;
-use crate::constants::*;
+use crate::resources::{SimRng, SpeciesConfig, SpeciesMeshes, Tank};
 use crate::utils::*;
 
 pub fn perf_startup(mut commands: Commands) {
     commands.spawn(PerfUiCompleteBundle::default());
 }
 
-pub fn fish_startup(
+pub fn camera_startup(mut commands: Commands) {
+    commands.spawn((Camera2dBundle::default(), CameraRig::default()));
+}
+
+pub fn population_startup(
     mut commands: Commands,
+    tank: Res<Tank>,
+    config: Res<SpeciesConfig>,
+    mut rng: ResMut<SimRng>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
-    commands.spawn(Camera2dBundle::default());
-    for _ in 0..NFISH {
-        let size = Size(random_in_range(FISH_SIZE_RANGE.0, FISH_SIZE_RANGE.1));
-        let position = if USE_CIRLCE {
-            Position::random_in_circle()
-        } else {
-            Position::random_in_square()
-        };
-        let rotation = Rotation::new(random_in_range(-PI, PI));
-        let speed = Speed(FISH_SPEED * size.0);
-        let vision = Vision::default() * size;
-        let fleeing = Fleeing::default();
-        let mesh = MaterialMesh2dBundle {
-            mesh: Mesh2dHandle(meshes.add(Triangle2d::new(
-                Vec2::new(10.0, 0.0) * size.0,
-                Vec2::new(-3.0, 3.0) * size.0,
-                Vec2::new(-3.0, -3.0) * size.0,
-            ))),
-            // material: materials.add(Color::rgb(0.0, 1.0, 0.0)),
-            material: materials.add(Color::hsl(
-                random_in_range(180.0, 250.0),
-                random_in_range(0.3, 0.7),
-                random_in_range(0.3, 0.7),
-            )),
-            transform: Transform::from_xyz(position.x, position.y, 0.5),
-            ..default()
-        };
-        commands.spawn((Fish, size, position, rotation, speed, vision, fleeing, mesh));
+    let species_meshes = SpeciesMeshes(
+        (0..config.species.len())
+            .map(|i| species_mesh(&config, SpeciesId(i), &mut meshes))
+            .collect(),
+    );
+    for p in &config.population {
+        let species = SpeciesId(config.find(&p.species).unwrap());
+        let mesh = &species_meshes.0[species.0];
+        for _ in 0..p.count {
+            let size = Size(config.species[species.0].size.sample(&mut rng.0));
+            let position = tank.random_position(&mut rng.0);
+            let rotation = Rotation::new(random_in_range(-PI, PI, &mut rng.0));
+            spawn_swimmer(
+                &mut commands,
+                &config,
+                species,
+                Genome::from(&config.species[species.0]),
+                size,
+                position,
+                rotation,
+                mesh.clone(),
+                &mut materials,
+                &mut rng.0,
+            );
+        }
     }
+    commands.insert_resource(species_meshes);
 }
 
-pub fn sharks_startup(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-) {
-    for _ in 0..NSHARKS {
-        let size = Size(random_in_range(SHARK_SIZE_RANGE.0, SHARK_SIZE_RANGE.1));
-        let position = Position::default();
-        let rotation = Rotation::default();
-        let speed = Speed(SHARK_SPEED * size.0);
-        let mut vision = Vision::default() * size;
-        vision.angle = PI / 3.0;
-        let mesh = MaterialMesh2dBundle {
-            mesh: Mesh2dHandle(meshes.add(Triangle2d::new(
-                Vec2::new(10.0, 0.0) * size.0,
-                Vec2::new(-3.0, 3.0) * size.0,
-                Vec2::new(-3.0, -3.0) * size.0,
-            ))),
-            material: materials.add(Color::rgb(0.75, 0.75, 0.75)),
-            transform: Transform::from_xyz(position.x, position.y, 0.5),
-            ..default()
-        };
-        commands.spawn((Shark, size, position, rotation, speed, vision, mesh));
+/// one mesh per species at size 1, swimmers are scaled by their transform
+pub fn species_mesh(
+    config: &SpeciesConfig,
+    species: SpeciesId,
+    meshes: &mut Assets<Mesh>,
+) -> Mesh2dHandle {
+    let Body([nose, left, right]) = config.species[species.0].shape.body();
+    Mesh2dHandle(meshes.add(Triangle2d::new(nose, left, right)))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_swimmer(
+    commands: &mut Commands,
+    config: &SpeciesConfig,
+    species: SpeciesId,
+    genome: Genome,
+    size: Size,
+    position: Position,
+    rotation: Rotation,
+    mesh: Mesh2dHandle,
+    materials: &mut Assets<ColorMaterial>,
+    rng: &mut impl Rng,
+) -> Entity {
+    let s = &config.species[species.0];
+    let speed = Speed(genome.speed * size.0);
+    let vision = Vision::new(genome.vision_distance, genome.vision_angle) * size;
+    let color = s.palette.sample(rng);
+    let mesh = MaterialMesh2dBundle {
+        mesh,
+        material: materials.add(color),
+        transform: Transform::from_xyz(position.x, position.y, 0.5)
+            .with_rotation(Quat::from_rotation_z(rotation.0))
+            .with_scale(Vec3::splat(size.0)),
+        ..default()
+    };
+    let swimmer = (
+        species,
+        size,
+        position,
+        rotation,
+        speed,
+        vision,
+        TurnRate(s.turn_rate),
+        Noise(s.noise),
+        genome.weights,
+        s.shape.body(),
+        Coloring(color),
+        Steering::default(),
+        Life::staggered(rng),
+        Motion {
+            velocity: rotation.to_velocity(speed).0,
+            max_force: s.max_force * size.0,
+        },
+        mesh,
+    );
+    let mut entity = if s.predator {
+        commands.spawn((Shark, Hunter::new(speed), genome, swimmer))
+    } else {
+        commands.spawn((Fish, Fleeing::default(), Alarm::default(), genome, swimmer))
+    };
+    if let Some(brain) = &s.brain {
+        entity.insert(brain.clone());
     }
+    entity.id()
 }
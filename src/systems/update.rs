@@ -1,11 +1,35 @@
-mod avoid_walls;
+mod behaviors;
+mod camera;
+mod collisions;
+mod evolution;
+mod fit_tank;
 mod fleeing;
+mod flow;
+mod food;
+mod hunting;
+mod inspector;
+mod lifecycle;
 mod movement;
-mod sac;
-mod wander;
+mod overlays;
+mod render_tank;
+mod scents;
+mod steer;
+mod visibility;
 
-pub use avoid_walls::*;
+pub use behaviors::*;
+pub use camera::*;
+pub use collisions::*;
+pub use evolution::*;
+pub use fit_tank::*;
 pub use fleeing::*;
+pub use flow::*;
+pub use food::*;
+pub use hunting::*;
+pub use inspector::*;
+pub use lifecycle::*;
 pub use movement::*;
-pub use sac::*;
-pub use wander::*;
+pub use overlays::*;
+pub use render_tank::*;
+pub use scents::*;
+pub use steer::*;
+pub use visibility::*;
@@ -0,0 +1,155 @@
+use crate::behaviors::{senses, Environment, PreySelection, SteeringBehaviors, Swimmer};
+use crate::components::*;
+use crate::resources::{
+    Actions, Confusion, Flow, Kinematics, Observations, Scents, SimRng, SpatialIndex, Tank,
+    VisibilityMap,
+};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::cell::RefCell;
+
+type Swimmers<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static SpeciesId,
+        &'static Position,
+        &'static Rotation,
+        &'static Speed,
+        &'static Size,
+        &'static Vision,
+        &'static Weights,
+        &'static Noise,
+        &'static Coloring,
+        &'static Motion,
+        Option<&'static Fleeing>,
+        Option<&'static Hunter>,
+        Has<Shark>,
+    ),
+>;
+
+/// everything needed to see the tank the way behaviors do
+#[derive(SystemParam)]
+pub struct Snapshot<'w, 's> {
+    kinematics: Res<'w, Kinematics>,
+    confusion: Res<'w, Confusion>,
+    selection: Res<'w, PreySelection>,
+    scents: Res<'w, Scents>,
+    flow: Res<'w, Flow>,
+    tank: Res<'w, Tank>,
+    index: Res<'w, SpatialIndex>,
+    visibility: Res<'w, VisibilityMap>,
+    actions: Res<'w, Actions>,
+    rng: ResMut<'w, SimRng>,
+    swimmers: Swimmers<'w, 's>,
+    food: Query<'w, 's, (Entity, &'static Position, &'static Size), With<Food>>,
+    brains: Query<'w, 's, (Entity, &'static Brain)>,
+}
+
+impl Snapshot<'_, '_> {
+    /// calls f with every swimmer as it is now, the swimmers it can see, and the rest of the tank
+    pub fn each(&mut self, mut f: impl FnMut(&Swimmer, &[&Swimmer], &Environment)) {
+        let swimmers = self
+            .swimmers
+            .iter()
+            .map(
+                |(e, species, p, r, speed, size, v, w, n, c, m, f, h, predator)| {
+                    let velocity = match *self.kinematics {
+                        Kinematics::Heading => r.to_velocity(*speed).0,
+                        Kinematics::Reynolds => m.velocity,
+                    };
+                    let swimmer = Swimmer {
+                        entity: e,
+                        species: *species,
+                        predator,
+                        fleeing: f.is_some_and(|f| f.active),
+                        hunter: h.copied(),
+                        position: *p,
+                        rotation: *r,
+                        velocity,
+                        speed: *speed,
+                        size: *size,
+                        vision: *v,
+                        weights: *w,
+                        noise: *n,
+                        color: *c,
+                    };
+                    (e, swimmer)
+                },
+            )
+            .collect::<HashMap<_, _>>();
+        let food = self
+            .food
+            .iter()
+            .map(|(e, p, s)| (e, (*p, *s)))
+            .collect::<HashMap<_, _>>();
+        let brains = self.brains.iter().collect::<HashMap<_, _>>();
+        let rng = RefCell::new(&mut self.rng.0);
+        let env = Environment {
+            tank: &self.tank,
+            index: &self.index,
+            swimmers: &swimmers,
+            food: &food,
+            confusion: *self.confusion,
+            selection: *self.selection,
+            scents: &self.scents,
+            flow: &self.flow,
+            brains: &brains,
+            actions: &self.actions,
+            rng: &rng,
+        };
+        for (e, agent) in &swimmers {
+            let neighbors = self
+                .visibility
+                .get(e)
+                .into_iter()
+                .flatten()
+                .filter_map(|n| swimmers.get(n))
+                .collect::<Vec<_>>();
+            f(agent, &neighbors, &env);
+        }
+    }
+}
+
+/// runs every registered steering behavior on every swimmer. behaviors see the tank as it was at
+/// the start of the tick so the order they run in doesn't matter
+pub fn apply_behaviors(
+    behaviors: Res<SteeringBehaviors>,
+    mut snapshot: Snapshot,
+    mut steering: Query<&mut Steering>,
+) {
+    snapshot.each(|agent, neighbors, env| {
+        let Ok(mut steering) = steering.get_mut(agent.entity) else {
+            return;
+        };
+        let steered =
+            env.brains.contains_key(&agent.entity) || env.actions.0.contains_key(&agent.entity);
+        for behavior in &behaviors.0 {
+            if steered && !behavior.with_brain() {
+                continue;
+            }
+            if let Some(desire) = behavior.steer(agent, neighbors, env) {
+                steering.add_with_speed(
+                    behavior.name(),
+                    desire.heading,
+                    desire.speed,
+                    desire.weight,
+                    behavior.priority(),
+                );
+            }
+        }
+    });
+}
+
+/// records what every swimmer senses, for anything outside the simulation that wants to steer
+/// them. isn't part of the schedule, run it after compute_visibility when it's wanted
+pub fn observe(mut snapshot: Snapshot, mut observations: ResMut<Observations>) {
+    observations.0.clear();
+    snapshot.each(|agent, neighbors, env| {
+        observations
+            .0
+            .insert(agent.entity, senses(agent, neighbors, env));
+    });
+}
@@ -0,0 +1,106 @@
+use crate::components::{CameraRig, Follow, IsFish, Position, Selected};
+use crate::constants::{FOLLOW_RATE, ZOOM_RANGE};
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use rand::random;
+
+/// drag with the right mouse button to pan. panning stops any follow
+pub fn pan_camera(
+    buttons: Res<ButtonInput<MouseButton>>,
+    mut motion: EventReader<MouseMotion>,
+    mut cameras: Query<(&mut Transform, &OrthographicProjection, &mut CameraRig)>,
+) {
+    let delta: Vec2 = motion.read().map(|m| m.delta).sum();
+    if !buttons.pressed(MouseButton::Right) || delta == Vec2::ZERO {
+        return;
+    }
+    for (mut t, projection, mut rig) in &mut cameras {
+        rig.follow = Follow::Free;
+        // screen y points down, world y points up
+        t.translation.x -= delta.x * projection.scale;
+        t.translation.y += delta.y * projection.scale;
+    }
+}
+
+pub fn zoom_camera(
+    mut wheel: EventReader<MouseWheel>,
+    mut cameras: Query<&mut OrthographicProjection, With<CameraRig>>,
+) {
+    let scroll: f32 = wheel
+        .read()
+        .map(|w| match w.unit {
+            MouseScrollUnit::Line => w.y,
+            MouseScrollUnit::Pixel => w.y / 100.0,
+        })
+        .sum();
+    if scroll == 0.0 {
+        return;
+    }
+    for mut projection in &mut cameras {
+        projection.scale =
+            (projection.scale * 0.9_f32.powf(scroll)).clamp(ZOOM_RANGE.0, ZOOM_RANGE.1);
+    }
+}
+
+/// R resets the view, F cycles through follow modes, tab selects another fish
+pub fn camera_keys(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut cameras: Query<(&mut Transform, &mut OrthographicProjection, &mut CameraRig)>,
+    fish: Query<Entity, IsFish>,
+    selected: Query<Entity, With<Selected>>,
+) {
+    for (mut t, mut projection, mut rig) in &mut cameras {
+        if keys.just_pressed(KeyCode::KeyR) {
+            t.translation.x = 0.0;
+            t.translation.y = 0.0;
+            projection.scale = 1.0;
+            rig.follow = Follow::Free;
+        }
+        if keys.just_pressed(KeyCode::KeyF) {
+            rig.follow = rig.follow.next();
+        }
+    }
+    if keys.just_pressed(KeyCode::Tab) {
+        let n = fish.iter().len();
+        if let Some(e) = fish.iter().nth((random::<f32>() * n as f32) as usize) {
+            select(&mut commands, &selected, e);
+        }
+    }
+}
+
+pub fn select(commands: &mut Commands, selected: &Query<Entity, With<Selected>>, e: Entity) {
+    for previous in selected {
+        commands.entity(previous).remove::<Selected>();
+    }
+    commands.entity(e).insert(Selected);
+}
+
+pub fn follow_camera(
+    time: Res<Time>,
+    mut cameras: Query<(&mut Transform, &CameraRig)>,
+    fish: Query<&Position, IsFish>,
+    selected: Query<&Position, With<Selected>>,
+) {
+    for (mut t, rig) in &mut cameras {
+        let target = match rig.follow {
+            Follow::Free => continue,
+            Follow::Selected => match selected.get_single() {
+                Ok(p) => p.0,
+                Err(_) => continue,
+            },
+            Follow::School => {
+                let n = fish.iter().len();
+                if n == 0 {
+                    continue;
+                }
+                fish.iter().map(|p| p.0).sum::<Vec2>() / n as f32
+            }
+        };
+        let step = (FOLLOW_RATE * time.delta_seconds()).min(1.0);
+        let current = t.translation.truncate();
+        let next = current.lerp(target, step);
+        t.translation.x = next.x;
+        t.translation.y = next.y;
+    }
+}
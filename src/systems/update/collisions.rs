@@ -0,0 +1,53 @@
+use crate::components::{Body, IsFish, Position, Rotation, Size};
+use crate::resources::{SpatialIndex, Tank};
+use crate::utils::{body, overlap, reach};
+use bevy::math::Vec2;
+use bevy::prelude::{Entity, Query, Res, ResMut};
+
+pub fn index_positions(mut index: ResMut<SpatialIndex>, positioned: Query<(Entity, &Position)>) {
+    index.clear();
+    for (e, p) in &positioned {
+        index.insert(e, p.0);
+    }
+}
+
+/// pushes overlapping fish apart. each fish of an overlapping pair moves half of the way out
+pub fn collide(
+    index: Res<SpatialIndex>,
+    tank: Res<Tank>,
+    mut fish: Query<(Entity, &mut Position, &Rotation, &Size, &Body), IsFish>,
+) {
+    let largest = fish
+        .iter()
+        .map(|(_, _, _, s, b)| reach(*s, *b))
+        .fold(0.0, f32::max);
+
+    let mut pushes: Vec<(Entity, Vec2)> = Vec::new();
+    for (e1, p1, r1, s1, b1) in &fish {
+        let reach1 = reach(*s1, *b1);
+        let body1 = body(*p1, *r1, *s1, *b1);
+        let mut push = Vec2::ZERO;
+        for (e2, _) in index.nearby(p1.0, reach1 + largest) {
+            if e2 == e1 {
+                continue;
+            }
+            let Ok((_, p2, r2, s2, b2)) = fish.get(e2) else {
+                continue;
+            };
+            if p1.distance(*p2) > reach1 + reach(*s2, *b2) {
+                continue;
+            }
+            if let Some(v) = overlap(&body1, &body(*p2, *r2, *s2, *b2)) {
+                push += v / 2.0;
+            }
+        }
+        if push != Vec2::ZERO {
+            pushes.push((e1, push));
+        }
+    }
+
+    for (e, push) in pushes {
+        let (_, mut p, _, _, _) = fish.get_mut(e).unwrap();
+        *p = tank.contain(Position(p.0 + push), 0.0);
+    }
+}
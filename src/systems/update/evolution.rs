@@ -0,0 +1,99 @@
+use crate::components::{Genome, SpeciesId};
+use crate::constants::{GENE_SAMPLE, TIME_RATE};
+use crate::resources::{GeneStats, GenerationStats, SpeciesConfig};
+use bevy::prelude::{warn, Query, Res, ResMut, Time};
+use bevy::utils::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+/// summarizes the genes of each species and generation now and then, so we can watch them drift
+pub fn record_genes(
+    time: Res<Time>,
+    config: Res<SpeciesConfig>,
+    mut stats: ResMut<GeneStats>,
+    swimmers: Query<(&SpeciesId, &Genome)>,
+) {
+    let dt = time.delta().as_secs_f32() * TIME_RATE;
+    stats.elapsed += dt;
+    stats.since += dt;
+    if stats.since < GENE_SAMPLE {
+        return;
+    }
+    stats.since = 0.0;
+
+    let mut groups: HashMap<(usize, u32), Vec<&Genome>> = HashMap::default();
+    for (s, g) in &swimmers {
+        groups.entry((s.0, g.generation)).or_default().push(g);
+    }
+    let mut generations = groups
+        .into_iter()
+        .map(|((species, generation), genomes)| {
+            let n = genomes.len() as f32;
+            let genes = (0..genomes[0].genes().len())
+                .map(|i| {
+                    let values = genomes.iter().map(|g| g.genes()[i].1);
+                    let mean = values.clone().sum::<f32>() / n;
+                    let variance = values.map(|v| (v - mean).powi(2)).sum::<f32>() / n;
+                    (genomes[0].genes()[i].0, mean, variance.sqrt())
+                })
+                .collect();
+            GenerationStats {
+                species,
+                generation,
+                count: genomes.len(),
+                genes,
+            }
+        })
+        .collect::<Vec<_>>();
+    generations.sort_by_key(|g| (g.species, g.generation));
+
+    if let Some(path) = &config.gene_file {
+        let first = stats.samples == 0;
+        if let Err(e) = append_genes(path, &config, stats.elapsed, &generations, first) {
+            warn!("couldn't write genes to {path}: {e}");
+        }
+    }
+    stats.samples += 1;
+    stats.generations = generations;
+}
+
+// one row per species and generation, with the mean and deviation of each gene
+fn append_genes(
+    path: &str,
+    config: &SpeciesConfig,
+    elapsed: f32,
+    generations: &[GenerationStats],
+    first: bool,
+) -> std::io::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(!first)
+        .truncate(first)
+        .open(path)?;
+    if first {
+        let genes = generations.first().map_or(Vec::new(), |g| {
+            g.genes
+                .iter()
+                .map(|(name, _, _)| format!("{name}_mean,{name}_sd"))
+                .collect()
+        });
+        writeln!(file, "tick,species,generation,count,{}", genes.join(","))?;
+    }
+    for g in generations {
+        let genes = g
+            .genes
+            .iter()
+            .map(|(_, mean, sd)| format!("{mean},{sd}"))
+            .collect::<Vec<_>>();
+        writeln!(
+            file,
+            "{elapsed:.0},{},{},{},{}",
+            config.species[g.species].name,
+            g.generation,
+            g.count,
+            genes.join(",")
+        )?;
+    }
+    Ok(())
+}
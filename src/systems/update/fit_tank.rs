@@ -0,0 +1,36 @@
+use crate::components::{Position, Size};
+use crate::resources::Tank;
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized};
+
+/// sizes the tank to the window it starts in, before anyone is spawned into it. a maximized or
+/// fullscreen launch never sends a resize
+pub fn fit_tank_startup(windows: Query<&Window, With<PrimaryWindow>>, mut tank: ResMut<Tank>) {
+    if let Ok(window) = windows.get_single() {
+        tank.fit(window.width(), window.height());
+    }
+}
+
+pub fn fit_tank_to_window(
+    mut resized: EventReader<WindowResized>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+    mut tank: ResMut<Tank>,
+) {
+    let Ok(primary) = windows.get_single() else {
+        return;
+    };
+    // only the latest size matters when several resizes arrive in one frame
+    if let Some(e) = resized.read().filter(|e| e.window == primary).last() {
+        tank.fit(e.width, e.height);
+    }
+}
+
+/// when the walls move, anything left outside of them is pulled back in
+pub fn keep_in_tank(tank: Res<Tank>, mut swimmers: Query<(&mut Position, &Size)>) {
+    if !tank.is_changed() {
+        return;
+    }
+    for (mut p, s) in &mut swimmers {
+        *p = tank.contain(*p, 10.0 * s.0);
+    }
+}
@@ -1,39 +1,162 @@
 use bevy::prelude::*;
 
-use crate::can_see_position;
-use crate::components::{Fleeing, IsFish, IsShark, Position, Rotation, Size, Speed, Vision};
-use crate::constants::{FLIGHT_MAX, FLIGHT_SPEED};
+use crate::components::{
+    Alarm, Fleeing, Genome, IsFish, IsShark, Position, Rotation, Size, Speed, Vision,
+};
+use crate::constants::{
+    ALARM_LATENCY, ALARM_SENSITIVITY, FLIGHT_HYSTERESIS, FLIGHT_SPEED, STARTLE_DURATION, TIME_RATE,
+};
+use crate::resources::{SimRng, VisibilityMap};
+use crate::utils::{can_see_position, random_in_range};
+use rand::Rng;
 
+/// fish flee when they see a shark close enough to worry them, or when the alarm they picked up
+/// from other fish goes off
+#[allow(clippy::type_complexity)]
 pub fn start_fleeing(
-    mut fish: Query<(&Position, &mut Rotation, &mut Speed, &Vision, &mut Fleeing), IsFish>,
+    time: Res<Time>,
+    mut fish: Query<
+        (
+            &Position,
+            &Rotation,
+            &mut Speed,
+            &Vision,
+            &mut Fleeing,
+            &mut Alarm,
+            &Genome,
+        ),
+        IsFish,
+    >,
     sharks: Query<(&Size, &Position), IsShark>,
 ) {
-    for (p, mut r, mut s, v, mut f) in &mut fish {
-        if f.0 {
+    let dt = time.delta().as_secs_f32() * TIME_RATE;
+    for (p, r, mut s, v, mut f, mut a, g) in &mut fish {
+        if f.active {
             continue;
         }
-
-        for (ss, sp) in &sharks {
-            if can_see_position(*p, *r, *v, *ss, *sp) {
-                f.0 = true;
-                s.0 *= FLIGHT_SPEED;
-                *r = p.point_away(*sp);
+        let alarmed = match a.0 {
+            Some(ticks) if ticks <= dt => true,
+            Some(ticks) => {
+                a.0 = Some(ticks - dt);
+                false
             }
+            None => false,
+        };
+
+        let spotted = sharks.iter().any(|(ss, sp)| {
+            p.distance(*sp) < g.flee_distance && can_see_position(*p, *r, *v, *ss, *sp)
+        });
+        if alarmed || spotted {
+            f.active = true;
+            // a fish that saw the shark itself flees for as long as the shark is close
+            f.startled = if spotted { 0.0 } else { STARTLE_DURATION };
+            a.0 = None;
+            s.0 *= FLIGHT_SPEED;
         }
     }
 }
 
+/// calm fish that see others fleeing may startle too, after a short delay. the more fleeing fish
+/// in sight, the more likely. this is what sends waves of turning across a school
+pub fn spread_alarm(
+    time: Res<Time>,
+    visibility: Res<VisibilityMap>,
+    mut rng: ResMut<SimRng>,
+    mut fish: Query<(Entity, &mut Alarm), IsFish>,
+    fleeing: Query<&Fleeing>,
+) {
+    let dt = time.delta().as_secs_f32() * TIME_RATE;
+    for (e, mut a) in &mut fish {
+        if a.0.is_some() {
+            continue;
+        }
+        let Some(visible) = visibility.get(&e) else {
+            continue;
+        };
+        let alarms = visible
+            .iter()
+            .filter(|v| fleeing.get(**v).is_ok_and(|f| f.active))
+            .count();
+        if alarms == 0 {
+            continue;
+        }
+        let chance = 1.0 - (1.0 - ALARM_SENSITIVITY).powf(alarms as f32 * dt);
+        if rng.0.gen::<f32>() < chance {
+            a.0 = Some(ALARM_LATENCY * random_in_range(0.5, 1.5, &mut rng.0));
+        }
+    }
+}
+
+/// startled fish calm down once their startle wears off, and fish that saw a shark once it's far
+/// enough away. a startled fish that ends up near a shark keeps fleeing like it saw it. far enough
+/// is a bit further than the distance that set the fish off, so it doesn't flicker at the edge
 pub fn stop_fleeing(
-    mut fish: Query<(&Position, &mut Speed, &mut Fleeing), IsFish>,
+    time: Res<Time>,
+    mut fish: Query<(&Position, &mut Speed, &mut Fleeing, &Genome), IsFish>,
     sharks: Query<&Position, IsShark>,
 ) {
-    for (p, mut s, mut f) in &mut fish {
-        if !f.0 {
+    let dt = time.delta().as_secs_f32() * TIME_RATE;
+    for (p, mut s, mut f, g) in &mut fish {
+        if !f.active {
             continue;
         }
-        if sharks.iter().all(|sp| p.distance(*sp) > FLIGHT_MAX) {
-            f.0 = false;
+        if f.startled > 0.0 {
+            f.startled = (f.startled - dt).max(0.0);
+            if f.startled > 0.0 {
+                continue;
+            }
+        }
+        if sharks
+            .iter()
+            .all(|sp| p.distance(*sp) > g.flee_distance * FLIGHT_HYSTERESIS)
+        {
+            f.active = false;
             s.0 /= FLIGHT_SPEED;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Fish;
+    use crate::resources::SimRng;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::time::Duration;
+
+    #[test]
+    fn alarm_spreads_from_fleeing_fish_in_sight() {
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        // long enough that the alarm is as good as certain
+        time.advance_by(Duration::from_secs(10));
+        world.insert_resource(time);
+        world.insert_resource(SimRng::seeded(0));
+        let fleeing = Fleeing {
+            active: true,
+            startled: 0.0,
+        };
+        let bolted = world.spawn((Fish, fleeing, Alarm::default())).id();
+        let calm = world
+            .spawn((Fish, Fleeing::default(), Alarm::default()))
+            .id();
+        let watcher = world
+            .spawn((Fish, Fleeing::default(), Alarm::default()))
+            .id();
+        let alone = world
+            .spawn((Fish, Fleeing::default(), Alarm::default()))
+            .id();
+        let mut visibility = VisibilityMap::default();
+        visibility.insert(watcher, vec![bolted]);
+        visibility.insert(alone, vec![calm]);
+        world.insert_resource(visibility);
+
+        world.run_system_once(spread_alarm);
+        let alarm = |e| world.get::<Alarm>(e).unwrap().0;
+        let ticks = alarm(watcher).unwrap();
+        assert!((0.5 * ALARM_LATENCY..=1.5 * ALARM_LATENCY).contains(&ticks));
+        // calm fish in sight don't alarm anyone
+        assert!(alarm(alone).is_none());
+        assert!(alarm(calm).is_none());
+    }
+}
@@ -0,0 +1,7 @@
+use crate::constants::TIME_RATE;
+use crate::resources::Flow;
+use bevy::prelude::{Res, ResMut, Time};
+
+pub fn advance_flow(time: Res<Time>, mut flow: ResMut<Flow>) {
+    flow.time += time.delta().as_secs_f32() * TIME_RATE;
+}
@@ -0,0 +1,117 @@
+use crate::components::{Body, Food, IsFish, Life, Position, Rotation, Size};
+use crate::constants::{FEED_RATE, FOOD_DRIFT, FOOD_ENERGY, FOOD_SINK_SPEED, FOOD_SIZE, TIME_RATE};
+use crate::resources::{Flow, SimRng, SpatialIndex, Tank};
+use crate::utils::{body, contains, random_in_range, reach};
+use bevy::prelude::*;
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
+use bevy::window::PrimaryWindow;
+use rand::Rng;
+
+/// the point in the tank under the cursor, if the cursor is over the window
+pub fn cursor_position(
+    windows: &Query<&Window, With<PrimaryWindow>>,
+    cameras: &Query<(&Camera, &GlobalTransform)>,
+) -> Option<Position> {
+    let cursor = windows.get_single().ok()?.cursor_position()?;
+    let (camera, transform) = cameras.get_single().ok()?;
+    camera.viewport_to_world_2d(transform, cursor).map(Position)
+}
+
+pub fn drop_food(
+    mut commands: Commands,
+    buttons: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(position) = cursor_position(&windows, &cameras) else {
+        return;
+    };
+    spawn_food(&mut commands, position, &mut meshes, &mut materials);
+}
+
+/// pellets also fall now and then on their own, so the fish don't all starve
+pub fn feed(
+    mut commands: Commands,
+    time: Res<Time>,
+    tank: Res<Tank>,
+    mut rng: ResMut<SimRng>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let chance = FEED_RATE * time.delta().as_secs_f32();
+    if rng.0.gen::<f32>() < chance {
+        let position = tank.random_position(&mut rng.0);
+        spawn_food(&mut commands, position, &mut meshes, &mut materials);
+    }
+}
+
+pub fn spawn_food(
+    commands: &mut Commands,
+    position: Position,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) {
+    commands.spawn((
+        Food,
+        position,
+        Size(FOOD_SIZE),
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Circle::new(2.0 * FOOD_SIZE))),
+            material: materials.add(Color::rgb(0.8, 0.6, 0.3)),
+            transform: Transform::from_xyz(position.x, position.y, 0.4),
+            ..default()
+        },
+    ));
+}
+
+/// pellets sink slowly with a little sideways wobble and go with the current, then settle against
+/// the wall
+pub fn drift_food(
+    time: Res<Time>,
+    tank: Res<Tank>,
+    flow: Res<Flow>,
+    mut rng: ResMut<SimRng>,
+    mut food: Query<(&mut Position, &Size), With<Food>>,
+) {
+    let dt = time.delta().as_secs_f32() * TIME_RATE;
+    for (mut p, s) in &mut food {
+        let drift = Vec2::new(
+            random_in_range(-FOOD_DRIFT, FOOD_DRIFT, &mut rng.0),
+            random_in_range(-FOOD_DRIFT, FOOD_DRIFT, &mut rng.0) - FOOD_SINK_SPEED,
+        ) + flow.at(p.0);
+        *p = tank.contain(Position(p.0 + drift * dt), 2.0 * s.0);
+    }
+}
+
+/// any pellet touching a fish's body is eaten, and feeds that fish
+pub fn eat_food(
+    mut commands: Commands,
+    index: Res<SpatialIndex>,
+    mut fish: Query<(&Position, &Rotation, &Size, &Body, &mut Life), IsFish>,
+    food: Query<&Position, With<Food>>,
+) {
+    let mut eaten = Vec::new();
+    for (p, r, s, b, mut life) in &mut fish {
+        // pellets are small enough to count as points
+        let body = body(*p, *r, *s, *b);
+        for (e, _) in index.nearby(p.0, reach(*s, *b)) {
+            if eaten.contains(&e) {
+                continue;
+            }
+            if let Ok(fp) = food.get(e) {
+                if contains(&body, fp.0) {
+                    eaten.push(e);
+                    life.energy += FOOD_ENERGY;
+                }
+            }
+        }
+    }
+    for e in eaten {
+        commands.entity(e).despawn();
+    }
+}
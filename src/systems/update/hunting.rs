@@ -0,0 +1,260 @@
+use crate::behaviors::choose_prey;
+use crate::components::{Body, HuntState, Hunter, IsFish, Life, Position, Role, Size, Speed};
+use crate::constants::{
+    ALARM_RELEASE, ATTACK_DISTANCE, ATTACK_DRAIN, ATTACK_DURATION, ATTACK_MIN_ENERGY,
+    ENERGY_RECOVERY, HERD_SPREAD, PREY_ENERGY, REST_DURATION, TIME_RATE,
+};
+use crate::resources::{Confusion, HuntStats, Scents, SimRng, VisibilityMap};
+use crate::systems::Snapshot;
+use crate::utils::reach;
+use bevy::math::Vec2;
+use bevy::prelude::{Commands, Entity, ParamSet, Query, Res, ResMut, Time};
+use bevy::utils::{HashMap, HashSet};
+use rand::seq::IteratorRandom;
+use rand::Rng;
+
+/// moves sharks between patrolling, stalking, attacking and resting, and sets their speed to
+/// match. stalking and attacking sharks are the ones that hunt. a pack's target counts as in sight
+/// for every shark in the pack
+pub fn update_hunters(
+    time: Res<Time>,
+    visibility: Res<VisibilityMap>,
+    mut sharks: Query<(Entity, &Position, &mut Speed, &mut Hunter)>,
+    fish: Query<&Position, IsFish>,
+) {
+    let dt = time.delta().as_secs_f32() * TIME_RATE;
+    for (e, p, mut speed, mut h) in &mut sharks {
+        h.time += dt;
+        let nearest = visibility
+            .get(&e)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| fish.get(*e).ok())
+            .map(|fp| p.distance(*fp))
+            .fold(None, |nearest: Option<f32>, d| {
+                Some(nearest.map_or(d, |n| n.min(d)))
+            })
+            .or_else(|| Some(p.distance(*fish.get(h.target?).ok()?)));
+        match h.state {
+            HuntState::Patrol => {
+                h.energy = (h.energy + ENERGY_RECOVERY * dt).min(1.0);
+                if nearest.is_some() {
+                    h.enter(HuntState::Stalk);
+                }
+            }
+            HuntState::Stalk => {
+                h.energy = (h.energy + ENERGY_RECOVERY * dt).min(1.0);
+                match nearest {
+                    None => h.enter(HuntState::Patrol),
+                    Some(d) if d < ATTACK_DISTANCE && h.energy >= ATTACK_MIN_ENERGY => {
+                        h.enter(HuntState::Attack)
+                    }
+                    _ => {}
+                }
+            }
+            HuntState::Attack => {
+                h.energy = (h.energy - ATTACK_DRAIN * dt).max(0.0);
+                if nearest.is_none() || h.energy == 0.0 || h.time > ATTACK_DURATION {
+                    h.enter(HuntState::Rest);
+                }
+            }
+            HuntState::Rest => {
+                // resting recovers twice as fast as cruising
+                h.energy = (h.energy + 2.0 * ENERGY_RECOVERY * dt).min(1.0);
+                if h.time > REST_DURATION {
+                    h.enter(HuntState::Patrol);
+                }
+            }
+        }
+        speed.0 = h.cruising.0 * h.state.speed();
+    }
+}
+
+/// an attacking shark that reaches its target gets one strike at it. whether it catches the fish
+/// depends on how confused it is by all the fish it can see. either way the chase is over. a
+/// caught fish feeds the shark and releases alarm substance
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn strike(
+    mut commands: Commands,
+    confusion: Res<Confusion>,
+    mut stats: ResMut<HuntStats>,
+    mut scents: ResMut<Scents>,
+    visibility: Res<VisibilityMap>,
+    mut rng: ResMut<SimRng>,
+    mut sharks: Query<(
+        Entity,
+        &Position,
+        &Size,
+        &Body,
+        &mut Hunter,
+        Option<&mut Life>,
+    )>,
+    fish: Query<(&Position, &Size), IsFish>,
+) {
+    let mut caught = Vec::new();
+    for (e, p, s, b, mut h, life) in &mut sharks {
+        if h.state != HuntState::Attack {
+            continue;
+        }
+        // only the fish it's after counts, others in the way are just brushed past
+        let reach = reach(*s, *b);
+        let hit = h.target.filter(|t| {
+            !caught.contains(t) && fish.get(*t).is_ok_and(|(fp, _)| p.distance(*fp) < reach)
+        });
+        let Some(f) = hit else {
+            continue;
+        };
+        let visible = visibility
+            .get(&e)
+            .into_iter()
+            .flatten()
+            .filter(|v| fish.contains(**v))
+            .count();
+        stats.strikes += 1;
+        if rng.0.gen::<f32>() >= confusion.of(visible) {
+            stats.catches += 1;
+            caught.push(f);
+            let (fp, fs) = fish.get(f).unwrap();
+            scents.alarm.emit(fp.0, ALARM_RELEASE);
+            if let Some(mut life) = life {
+                life.energy += PREY_ENERGY * fs.0;
+            }
+        }
+        h.enter(HuntState::Rest);
+        h.target = None;
+    }
+    for f in caught {
+        commands.entity(f).despawn();
+    }
+}
+
+/// sharks that can see each other (or see a shark that sees another, and so on) hunt as a pack.
+/// the pack shares one target, the closest shark to it strikes and the rest herd
+pub fn coordinate_packs(
+    visibility: Res<VisibilityMap>,
+    mut sharks: Query<(Entity, &Position, &mut Hunter)>,
+    fish: Query<&Position, IsFish>,
+) {
+    let available = sharks
+        .iter()
+        .filter(|(_, _, h)| h.state != HuntState::Rest)
+        .map(|(e, p, h)| (e, (*p, h.target)))
+        .collect::<HashMap<_, _>>();
+    let mut seen = HashSet::new();
+    let mut packs = Vec::new();
+    for e in available.keys() {
+        if !seen.insert(*e) {
+            continue;
+        }
+        // sightings go both ways, either shark noticing the other is enough
+        let mut pack = vec![*e];
+        let mut i = 0;
+        while i < pack.len() {
+            let a = pack[i];
+            for b in available.keys() {
+                let sees =
+                    |x: Entity, y: Entity| visibility.get(&x).is_some_and(|v| v.contains(&y));
+                if !seen.contains(b) && (sees(a, *b) || sees(*b, a)) {
+                    seen.insert(*b);
+                    pack.push(*b);
+                }
+            }
+            i += 1;
+        }
+        packs.push(pack);
+    }
+
+    // sharks left out of a pack hunt alone, keeping whatever target they had
+    for (_, _, mut h) in &mut sharks {
+        h.role = Role::Solo;
+    }
+    for pack in packs.into_iter().filter(|p| p.len() > 1) {
+        let visible = pack
+            .iter()
+            .flat_map(|e| visibility.get(e).into_iter().flatten())
+            .filter(|f| fish.contains(**f))
+            .copied()
+            .collect::<HashSet<_>>();
+        // stick with the last target while anyone can still see it
+        let previous = pack
+            .iter()
+            .filter_map(|e| available[e].1)
+            .find(|t| visible.contains(t));
+        let center =
+            Position(pack.iter().map(|e| available[e].0 .0).sum::<Vec2>() / pack.len() as f32);
+        let target = previous.or_else(|| {
+            visible.iter().copied().min_by(|a, b| {
+                let da = fish.get(*a).unwrap().distance(center);
+                let db = fish.get(*b).unwrap().distance(center);
+                da.total_cmp(&db)
+            })
+        });
+        let Some(target) = target else {
+            continue;
+        };
+        let tp = *fish.get(target).unwrap();
+        let mut members = pack.clone();
+        members.sort_by(|a, b| {
+            let da = available[a].0.distance(tp);
+            let db = available[b].0.distance(tp);
+            da.total_cmp(&db)
+        });
+        let striker = members[0];
+        let herders = members.len() - 1;
+        for (i, e) in members.into_iter().enumerate() {
+            let (_, _, mut h) = sharks.get_mut(e).unwrap();
+            h.target = Some(target);
+            h.role = if i == 0 {
+                Role::Striker
+            } else {
+                let offset = (i - 1) as f32 - (herders - 1) as f32 / 2.0;
+                Role::Herder {
+                    striker,
+                    angle: offset * HERD_SPREAD,
+                }
+            };
+            if h.state == HuntState::Patrol {
+                h.enter(HuntState::Stalk);
+            }
+        }
+    }
+}
+
+/// solo sharks that are hunting stick with their target while they can see it. when they lose it
+/// they pick a new one, and that's when the fish in sight can confuse them into going after a
+/// random one instead of the one they'd prefer. sharks that aren't hunting let their target go
+pub fn pick_prey(mut params: ParamSet<(Snapshot, Query<&mut Hunter>)>) {
+    let mut picks = Vec::new();
+    params.p0().each(|agent, neighbors, env| {
+        let Some(h) = agent.hunter else {
+            return;
+        };
+        if h.role != Role::Solo {
+            return;
+        }
+        if !h.state.hunting() {
+            picks.push((agent.entity, None));
+            return;
+        }
+        let prey = neighbors.iter().filter(|n| !n.predator);
+        if h.target
+            .is_some_and(|t| prey.clone().any(|n| n.entity == t))
+        {
+            return;
+        }
+        let confusion = env.confusion.of(prey.clone().count());
+        let mut rng = env.rng.borrow_mut();
+        let target = if confusion > 0.0 && rng.gen::<f32>() < confusion {
+            prey.copied().choose(&mut **rng)
+        } else {
+            choose_prey(agent, neighbors, env)
+        };
+        picks.push((agent.entity, target.map(|t| t.entity)));
+    });
+    let mut sharks = params.p1();
+    for (e, target) in picks {
+        if let Ok(mut h) = sharks.get_mut(e) {
+            h.target = target;
+        }
+    }
+}
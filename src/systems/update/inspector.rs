@@ -0,0 +1,172 @@
+use crate::components::*;
+use crate::constants::TIME_RATE;
+use crate::resources::{HuntStats, Kinematics, SpatialIndex, SpeciesConfig, VisibilityMap};
+use crate::systems::{cursor_position, select};
+use crate::utils::{body, contains, reach};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+pub fn inspector_startup(mut commands: Commands) {
+    commands.spawn((
+        Inspector,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 16.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            right: Val::Px(10.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        })
+        .with_background_color(Color::rgba(0.0, 0.0, 0.0, 0.6)),
+    ));
+}
+
+/// clicking on a swimmer selects it, clicking on the selected swimmer again clears the selection.
+/// a click that lands on a swimmer is used up so it doesn't also drop food
+pub fn pick_swimmer(
+    mut commands: Commands,
+    mut buttons: ResMut<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    index: Res<SpatialIndex>,
+    swimmers: Query<(&Position, &Rotation, &Size, &Body)>,
+    selected: Query<Entity, With<Selected>>,
+) {
+    if !buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(cursor) = cursor_position(&windows, &cameras) else {
+        return;
+    };
+    let largest = swimmers
+        .iter()
+        .map(|(_, _, s, b)| reach(*s, *b))
+        .fold(0.0, f32::max);
+    let hit = index.nearby(cursor.0, largest).find(|(e, _)| {
+        swimmers
+            .get(*e)
+            .is_ok_and(|(p, r, s, b)| contains(&body(*p, *r, *s, *b), cursor.0))
+    });
+    let Some((e, _)) = hit else {
+        return;
+    };
+    buttons.clear_just_pressed(MouseButton::Left);
+    if selected.contains(e) {
+        commands.entity(e).remove::<Selected>();
+    } else {
+        select(&mut commands, &selected, e);
+    }
+}
+
+pub fn highlight_selected(
+    selected: Query<(&Position, &Size, &Body), With<Selected>>,
+    mut gizmos: Gizmos,
+) {
+    for (p, s, b) in &selected {
+        gizmos.circle_2d(p.0, reach(*s, *b) + 4.0, Color::YELLOW);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn update_inspector(
+    config: Res<SpeciesConfig>,
+    kinematics: Res<Kinematics>,
+    stats: Res<HuntStats>,
+    visibility: Res<VisibilityMap>,
+    selected: Query<
+        (
+            Entity,
+            &SpeciesId,
+            &Size,
+            &Speed,
+            &Rotation,
+            &Vision,
+            Option<&Fleeing>,
+            Option<&Alarm>,
+            Option<&Hunter>,
+            Option<&Life>,
+            Option<&Genome>,
+            &Steering,
+            &Motion,
+        ),
+        With<Selected>,
+    >,
+    mut panels: Query<(&mut Text, &mut Visibility), With<Inspector>>,
+) {
+    let Ok((mut text, mut shown)) = panels.get_single_mut() else {
+        return;
+    };
+    let Ok((e, species, size, speed, r, v, fleeing, alarm, hunter, life, genome, steering, m)) =
+        selected.get_single()
+    else {
+        *shown = Visibility::Hidden;
+        return;
+    };
+    *shown = Visibility::Visible;
+
+    let visible = visibility.get(&e).map(Vec::as_slice).unwrap_or_default();
+    let mut neighbors = visible
+        .iter()
+        .take(8)
+        .map(|n| format!("{n:?}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if visible.len() > 8 {
+        neighbors += ", ...";
+    }
+    let speed = match *kinematics {
+        Kinematics::Heading => format!("{:.2}", speed.0),
+        Kinematics::Reynolds => format!("{:.2} of {:.2}", m.velocity.length(), speed.0),
+    };
+    let alarmed = match alarm.and_then(|a| a.0) {
+        Some(ticks) => format!(" (startles in {ticks:.0})"),
+        None => String::new(),
+    };
+    let generation = genome.map_or(String::new(), |g| format!(", generation {}", g.generation));
+    // ages are shown in seconds of simulated time
+    let life = life.map_or(String::new(), |l| {
+        format!(
+            "\nage       {:.0}s, energy {:.2}{generation}",
+            l.age / TIME_RATE,
+            l.energy
+        )
+    });
+    let hunting = hunter.map_or(String::new(), |h| {
+        format!(
+            "\nhunting   {:?} as {:?}, energy {:.2}\n\
+             caught    {} of {} strikes",
+            h.state, h.role, h.energy, stats.catches, stats.strikes
+        )
+    });
+    // turns are shown in degrees, they are easier to read than radians
+    let mut rules = String::new();
+    for (rule, turn) in steering.wanted() {
+        rules += &format!("\n{rule:<10} {:+.2}", turn.to_degrees());
+    }
+    text.sections[0].value = format!(
+        "{e:?} ({})\n\
+         size      {:.2}\n\
+         speed     {speed}\n\
+         rotation  {:.1}\n\
+         vision    {:.1} at {:.1}\n\
+         fleeing   {}{alarmed}{hunting}{life}\n\
+         visible   {} [{neighbors}]\n\
+         {rules}\n\
+         turned     {:+.2}",
+        config.species[species.0].name,
+        size.0,
+        r.0.to_degrees(),
+        v.distance,
+        v.angle.to_degrees(),
+        fleeing.is_some_and(|f| f.active),
+        visible.len(),
+        steering.turn.to_degrees(),
+    );
+}
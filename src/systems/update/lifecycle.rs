@@ -0,0 +1,315 @@
+use crate::components::{
+    Brain, Fleeing, Genome, Hunter, Life, Motion, Position, Rotation, Size, SpeciesId, Speed,
+    Vision,
+};
+use crate::constants::{
+    ADULT_AGE, BREEDING_COOLDOWN, BREEDING_COST, BREEDING_ENERGY, FLIGHT_SPEED, GROWTH, MAX_SIZE,
+    METABOLISM, MORTALITY, OFFSPRING_SIZE, POPULATION_HISTORY, POPULATION_SAMPLE, SENESCENCE,
+    TIME_RATE,
+};
+use crate::resources::{
+    PopulationHistory, SimRng, SpeciesConfig, SpeciesMeshes, Tank, VisibilityMap,
+};
+use crate::systems::spawn_swimmer;
+use crate::utils::random_in_range;
+use bevy::prelude::*;
+use rand::Rng;
+use std::f32::consts::PI;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+/// swimmers age and burn energy. surplus energy from eating grows them, which scales everything
+/// that depends on size. they die of old age now and then, and always when they run out of energy
+#[allow(clippy::type_complexity)]
+pub fn live(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rng: ResMut<SimRng>,
+    mut swimmers: Query<(
+        Entity,
+        &mut Life,
+        &mut Size,
+        &mut Speed,
+        &mut Vision,
+        &mut Motion,
+        &mut Transform,
+        Option<&Fleeing>,
+        Option<&mut Hunter>,
+    )>,
+) {
+    let dt = time.delta().as_secs_f32() * TIME_RATE;
+    for (e, mut life, mut size, mut speed, mut vision, mut motion, mut transform, f, hunter) in
+        &mut swimmers
+    {
+        life.age += dt;
+        life.cooldown = (life.cooldown - dt).max(0.0);
+        let effort = if f.is_some_and(|f| f.active) {
+            FLIGHT_SPEED
+        } else {
+            1.0
+        };
+        life.energy -= METABOLISM * size.0 * effort * dt;
+        if life.energy > 1.0 {
+            // swimmers born bigger than MAX_SIZE, like large sharks, just stop growing
+            let grown = (size.0 + (life.energy - 1.0) * GROWTH)
+                .min(MAX_SIZE)
+                .max(size.0);
+            life.energy = 1.0;
+            // speed, vision and force are all proportional to size, fleeing or not
+            let ratio = grown / size.0;
+            size.0 = grown;
+            speed.0 *= ratio;
+            vision.distance *= ratio;
+            motion.max_force *= ratio;
+            transform.scale = Vec3::splat(grown);
+            if let Some(mut h) = hunter {
+                h.cruising.0 *= ratio;
+            }
+        }
+        let hazard = MORTALITY * 2f32.powf(life.age / SENESCENCE);
+        if life.energy <= 0.0 || rng.0.gen::<f32>() < hazard * dt {
+            commands.entity(e).despawn();
+        }
+    }
+}
+
+/// well fed adults that can see a well fed adult of their own species have an offspring between
+/// them. it's a small version of its species with a mix of its parents' genes (and brains, if
+/// they have them), and the energy they gave up
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn breed(
+    mut commands: Commands,
+    config: Res<SpeciesConfig>,
+    meshes: Res<SpeciesMeshes>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    tank: Res<Tank>,
+    visibility: Res<VisibilityMap>,
+    mut rng: ResMut<SimRng>,
+    mut parents: Query<(
+        Entity,
+        &SpeciesId,
+        &Position,
+        &Genome,
+        &mut Life,
+        Option<&Brain>,
+    )>,
+) {
+    let ready = |life: &Life| {
+        life.age >= ADULT_AGE && life.energy >= BREEDING_ENERGY && life.cooldown == 0.0
+    };
+    let mut bred: Vec<Entity> = Vec::new();
+    let mut births = Vec::new();
+    for (e, species, p, g, life, brain) in &parents {
+        if !ready(life) || bred.contains(&e) {
+            continue;
+        }
+        let mate = visibility.get(&e).into_iter().flatten().find(|m| {
+            !bred.contains(m)
+                && parents
+                    .get(**m)
+                    .is_ok_and(|(_, s, _, _, l, _)| s == species && ready(l))
+        });
+        if let Some(m) = mate {
+            bred.extend([e, *m]);
+            let (_, _, mp, mg, _, mb) = parents.get(*m).unwrap();
+            let genome = Genome::offspring(g, mg, &mut rng.0);
+            let brain = match (brain, mb) {
+                (Some(a), Some(b)) => Some(Brain::offspring(a, b, &mut rng.0)),
+                (a, b) => a.or(b).map(|a| Brain::offspring(a, a, &mut rng.0)),
+            };
+            births.push((*species, genome, brain, Position((p.0 + mp.0) / 2.0)));
+        }
+    }
+    for e in bred {
+        let (_, _, _, _, mut life, _) = parents.get_mut(e).unwrap();
+        life.energy -= BREEDING_COST / 2.0;
+        life.cooldown = BREEDING_COOLDOWN;
+    }
+    for (species, genome, brain, p) in births {
+        let size = Size(config.species[species.0].size.sample(&mut rng.0) * OFFSPRING_SIZE);
+        let rotation = Rotation::new(random_in_range(-PI, PI, &mut rng.0));
+        let child = spawn_swimmer(
+            &mut commands,
+            &config,
+            species,
+            genome,
+            size,
+            tank.contain(p, 0.0),
+            rotation,
+            meshes.0[species.0].clone(),
+            &mut materials,
+            &mut rng.0,
+        );
+        commands.entity(child).insert(Life::new(BREEDING_COST));
+        // the child's own brain replaces the one its species starts with
+        if let Some(brain) = brain {
+            commands.entity(child).insert(brain);
+        }
+    }
+}
+
+/// counts every species now and then, for plotting populations over time
+pub fn record_population(
+    time: Res<Time>,
+    config: Res<SpeciesConfig>,
+    mut history: ResMut<PopulationHistory>,
+    swimmers: Query<&SpeciesId>,
+) {
+    history.since += time.delta().as_secs_f32() * TIME_RATE;
+    if history.since < POPULATION_SAMPLE {
+        return;
+    }
+    history.since = 0.0;
+    let mut counts = vec![0; config.species.len()];
+    for s in &swimmers {
+        counts[s.0] += 1;
+    }
+    if let Some(path) = &config.population_file {
+        if let Err(e) = append_population(path, &config, &counts, history.samples.is_empty()) {
+            warn!("couldn't write populations to {path}: {e}");
+        }
+    }
+    history.samples.push_back(counts);
+    if history.samples.len() > POPULATION_HISTORY {
+        history.samples.pop_front();
+    }
+}
+
+fn append_population(
+    path: &str,
+    config: &SpeciesConfig,
+    counts: &[usize],
+    first: bool,
+) -> std::io::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(!first)
+        .truncate(first)
+        .open(path)?;
+    if first {
+        let names = config.species.iter().map(|s| s.name.as_str());
+        writeln!(file, "{}", names.collect::<Vec<_>>().join(","))?;
+    }
+    let counts = counts.iter().map(usize::to_string);
+    writeln!(file, "{}", counts.collect::<Vec<_>>().join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Fish, Shark};
+    use bevy::ecs::system::RunSystemOnce;
+    use std::time::Duration;
+
+    fn world(ticks: f32) -> World {
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs_f32(ticks / TIME_RATE));
+        world.insert_resource(time);
+        world.insert_resource(SimRng::seeded(0));
+        world
+    }
+
+    fn swimmer(world: &mut World, size: f32, energy: f32) -> Entity {
+        world
+            .spawn((
+                Life::new(energy),
+                Size(size),
+                Speed(size),
+                Vision::new(10.0 * size, 1.0),
+                Motion {
+                    velocity: Vec2::X,
+                    max_force: size,
+                },
+                Transform::default(),
+            ))
+            .id()
+    }
+
+    #[test]
+    fn surplus_energy_grows_everything_with_size() {
+        let mut world = world(1.0);
+        let fish = swimmer(&mut world, 1.0, 1.5);
+        world.entity_mut(fish).insert(Fish);
+        world.run_system_once(live);
+        let size = world.get::<Size>(fish).unwrap().0;
+        let surplus = 0.5 - METABOLISM;
+        assert!((size - (1.0 + surplus * GROWTH)).abs() < 1e-5);
+        assert_eq!(world.get::<Life>(fish).unwrap().energy, 1.0);
+        assert!((world.get::<Speed>(fish).unwrap().0 - size).abs() < 1e-5);
+        assert!((world.get::<Vision>(fish).unwrap().distance - 10.0 * size).abs() < 1e-4);
+        assert!((world.get::<Motion>(fish).unwrap().max_force - size).abs() < 1e-5);
+        assert_eq!(
+            world.get::<Transform>(fish).unwrap().scale,
+            Vec3::splat(size)
+        );
+    }
+
+    #[test]
+    fn big_swimmers_never_shrink_from_eating() {
+        let mut world = world(1.0);
+        let shark = swimmer(&mut world, MAX_SIZE + 2.0, 1.5);
+        world.entity_mut(shark).insert(Shark);
+        world.run_system_once(live);
+        assert_eq!(world.get::<Size>(shark).unwrap().0, MAX_SIZE + 2.0);
+        assert_eq!(world.get::<Speed>(shark).unwrap().0, MAX_SIZE + 2.0);
+    }
+
+    #[test]
+    fn starving_swimmers_die() {
+        let mut world = world(10.0);
+        let starving = swimmer(&mut world, 1.0, 5.0 * METABOLISM);
+        let fed = swimmer(&mut world, 1.0, 1.0);
+        world.run_system_once(live);
+        assert!(world.get_entity(starving).is_none());
+        assert!((world.get::<Life>(fed).unwrap().energy - (1.0 - 10.0 * METABOLISM)).abs() < 1e-6);
+    }
+
+    fn adult(world: &mut World, config: &SpeciesConfig, energy: f32) -> Entity {
+        let life = Life {
+            age: ADULT_AGE,
+            ..Life::new(energy)
+        };
+        let genome = Genome::from(&config.species[0]);
+        world
+            .spawn((SpeciesId(0), Position::new(0.0, 0.0), genome, life))
+            .id()
+    }
+
+    #[test]
+    fn adults_that_see_each_other_breed() {
+        let mut world = world(1.0);
+        let config = SpeciesConfig::default();
+        world.insert_resource(SpeciesMeshes(vec![default(); config.species.len()]));
+        world.init_resource::<Assets<ColorMaterial>>();
+        world.init_resource::<Tank>();
+        let a = adult(&mut world, &config, 0.9);
+        let b = adult(&mut world, &config, BREEDING_ENERGY);
+        let tired = adult(&mut world, &config, 0.9);
+        world.get_mut::<Life>(tired).unwrap().cooldown = 1.0;
+        let mut visibility = VisibilityMap::default();
+        visibility.insert(a, vec![b]);
+        visibility.insert(b, vec![a]);
+        visibility.insert(tired, vec![a]);
+        world.insert_resource(visibility);
+        world.insert_resource(config);
+
+        world.run_system_once(breed);
+        let life = |world: &World, e| *world.get::<Life>(e).unwrap();
+        for (parent, energy) in [(a, 0.9), (b, BREEDING_ENERGY)] {
+            let life = life(&world, parent);
+            assert!((life.energy - (energy - BREEDING_COST / 2.0)).abs() < 1e-6);
+            assert_eq!(life.cooldown, BREEDING_COOLDOWN);
+        }
+        // cooling down, and a and b were already taken
+        assert_eq!(life(&world, tired).energy, 0.9);
+        let children = world
+            .query::<(&Genome, &Life)>()
+            .iter(&world)
+            .filter(|(g, _)| g.generation == 1)
+            .map(|(_, l)| l.energy)
+            .collect::<Vec<_>>();
+        assert_eq!(children, vec![BREEDING_COST]);
+    }
+}
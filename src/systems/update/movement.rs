@@ -1,22 +1,37 @@
-use crate::components::{Position, Rotation, Speed};
-use crate::constants::{BOUNDS, HEIGHT, TIME_RATE, WIDTH};
+use crate::components::{Motion, Position, Rotation, Speed};
+use crate::constants::TIME_RATE;
+use crate::resources::{Flow, Kinematics, Tank};
+use crate::utils::Velocity;
 use bevy::math::Quat;
 use bevy::prelude::{Query, Res, Time, Transform};
 
-pub fn movement(time: Res<Time>, mut moveable: Query<(&mut Position, &Rotation, &Speed)>) {
-    for (mut p, r, s) in &mut moveable {
-        *p += r.to_velocity(*s) * time.delta().as_secs_f32() * TIME_RATE;
+pub fn movement(
+    time: Res<Time>,
+    tank: Res<Tank>,
+    kinematics: Res<Kinematics>,
+    flow: Res<Flow>,
+    mut moveable: Query<(&mut Position, &Rotation, &Speed, Option<&Motion>)>,
+) {
+    let [minx, maxx, miny, maxy] = tank.bounds();
+    let (width, height) = (maxx - minx, maxy - miny);
+    for (mut p, r, s, m) in &mut moveable {
+        let velocity = match (*kinematics, m) {
+            (Kinematics::Reynolds, Some(m)) => Velocity(m.velocity),
+            _ => r.to_velocity(*s),
+        };
+        // the current carries swimmers along on top of their own swimming
+        let velocity = Velocity(velocity.0 + flow.at(p.0));
+        *p += velocity * time.delta().as_secs_f32() * TIME_RATE;
         // in case a fish does get outside the tank, wrap it back around
-        let [minx, maxx, miny, maxy] = BOUNDS;
         if p.x > maxx {
-            p.x -= WIDTH;
+            p.x -= width;
         } else if p.x < minx {
-            p.x += WIDTH;
+            p.x += width;
         }
         if p.y > maxy {
-            p.y -= HEIGHT;
+            p.y -= height;
         } else if p.y < miny {
-            p.y += HEIGHT;
+            p.y += height;
         }
     }
 }
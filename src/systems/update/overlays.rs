@@ -0,0 +1,252 @@
+use crate::components::{Genome, IsFish, IsShark, Position, Rotation, Steering, Vision};
+use crate::constants::{POPULATION_HISTORY, STEERING_SCALE, WALL_AVOIDANCE};
+use crate::resources::{
+    Flow, Overlays, PopulationHistory, Scents, SpatialIndex, Tank, TankShape, VisibilityMap,
+};
+use crate::utils::{distance_to_circle_wall, distance_to_obstacle, distance_to_square_wall};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use std::f32::consts::PI;
+
+/// 1 vision cones, 2 visible neighbors, 3 wall probes, 4 steering, 5 flee radius, 6 spatial grid,
+/// 7 scents, 8 water flow, 9 population plot
+pub fn toggle_overlays(keys: Res<ButtonInput<KeyCode>>, mut overlays: ResMut<Overlays>) {
+    let overlays = &mut *overlays;
+    let layers = [
+        (KeyCode::Digit1, &mut overlays.vision),
+        (KeyCode::Digit2, &mut overlays.neighbors),
+        (KeyCode::Digit3, &mut overlays.probes),
+        (KeyCode::Digit4, &mut overlays.steering),
+        (KeyCode::Digit5, &mut overlays.flee),
+        (KeyCode::Digit6, &mut overlays.grid),
+        (KeyCode::Digit7, &mut overlays.scent),
+        (KeyCode::Digit8, &mut overlays.flow),
+        (KeyCode::Digit9, &mut overlays.population),
+    ];
+    for (key, layer) in layers {
+        if keys.just_pressed(key) {
+            *layer = !*layer;
+        }
+    }
+}
+
+pub fn draw_vision(
+    overlays: Res<Overlays>,
+    swimmers: Query<(&Position, &Rotation, &Vision)>,
+    mut gizmos: Gizmos,
+) {
+    if !overlays.vision {
+        return;
+    }
+    let color = Color::rgba(1.0, 1.0, 1.0, 0.15);
+    for (p, r, v) in &swimmers {
+        // arcs are measured clockwise from +y, rotations counter-clockwise from +x
+        gizmos.arc_2d(p.0, PI / 2.0 - r.0, 2.0 * v.angle, v.distance, color);
+        for side in [-v.angle, v.angle] {
+            let edge = (*r + Rotation::new(side)).unit_vector() * v.distance;
+            gizmos.line_2d(p.0, p.0 + edge, color);
+        }
+    }
+}
+
+pub fn draw_neighbors(
+    overlays: Res<Overlays>,
+    visibility: Res<VisibilityMap>,
+    positions: Query<&Position>,
+    mut gizmos: Gizmos,
+) {
+    if !overlays.neighbors {
+        return;
+    }
+    for (e, visible) in visibility.iter() {
+        let Ok(p1) = positions.get(*e) else {
+            continue;
+        };
+        for e2 in visible {
+            if let Ok(p2) = positions.get(*e2) {
+                gizmos.line_2d(p1.0, p2.0, Color::rgba(0.4, 1.0, 0.4, 0.3));
+            }
+        }
+    }
+}
+
+/// the three rays the wall avoidance looks along, cut off at the first wall or obstacle. red when
+/// something is close enough to turn for
+pub fn draw_probes(
+    overlays: Res<Overlays>,
+    tank: Res<Tank>,
+    swimmers: Query<(&Position, &Rotation, &Vision)>,
+    mut gizmos: Gizmos,
+) {
+    if !overlays.probes {
+        return;
+    }
+    for (p, r, v) in &swimmers {
+        for offset in [0.0, WALL_AVOIDANCE, -WALL_AVOIDANCE] {
+            let probe = *r + Rotation::new(offset);
+            let wall = match tank.shape {
+                TankShape::Circle { radius } => distance_to_circle_wall(*p, probe, radius),
+                TankShape::Rectangle { bounds } => distance_to_square_wall(*p, probe, bounds),
+            };
+            let hit = tank
+                .obstacles
+                .iter()
+                .map(|o| distance_to_obstacle(*p, probe, *o))
+                .fold(wall, f32::min);
+            let color = if hit < v.distance {
+                Color::RED
+            } else {
+                Color::rgba(1.0, 0.6, 0.2, 0.3)
+            };
+            gizmos.line_2d(p.0, p.0 + probe.unit_vector() * hit.min(v.distance), color);
+        }
+    }
+}
+
+/// each rule's turn this tick as a line out to the side it asked to turn towards
+pub fn draw_steering(
+    overlays: Res<Overlays>,
+    swimmers: Query<(&Position, &Steering)>,
+    mut gizmos: Gizmos,
+) {
+    if !overlays.steering {
+        return;
+    }
+    for (p, steering) in &swimmers {
+        let left = steering.from.unit_vector().perp();
+        for (rule, turn) in steering.wanted() {
+            let color = match rule {
+                "separation" => Color::RED,
+                "alignment" => Color::BLUE,
+                "cohesion" => Color::GREEN,
+                "food" => Color::ORANGE,
+                "walls" | "obstacles" => Color::FUCHSIA,
+                "flee" => Color::YELLOW,
+                "hunt" => Color::CRIMSON,
+                "rheotaxis" => Color::CYAN,
+                "food scent" => Color::ORANGE,
+                "alarm scent" | "shark scent" => Color::YELLOW_GREEN,
+                _ => Color::GRAY,
+            };
+            gizmos.line_2d(p.0, p.0 + left * turn * STEERING_SCALE, color);
+        }
+    }
+}
+
+/// every fish has its own flee distance, so each shark gets a circle at the fish's average
+pub fn draw_flee_radius(
+    overlays: Res<Overlays>,
+    sharks: Query<&Position, IsShark>,
+    fish: Query<&Genome, IsFish>,
+    mut gizmos: Gizmos,
+) {
+    if !overlays.flee || fish.is_empty() {
+        return;
+    }
+    let radius = fish.iter().map(|g| g.flee_distance).sum::<f32>() / fish.iter().len() as f32;
+    for p in &sharks {
+        gizmos.circle_2d(p.0, radius, Color::rgba(1.0, 0.2, 0.2, 0.5));
+    }
+}
+
+pub fn draw_grid(overlays: Res<Overlays>, index: Res<SpatialIndex>, mut gizmos: Gizmos) {
+    if !overlays.grid {
+        return;
+    }
+    let size = Vec2::splat(index.cell_size);
+    for (x, y) in index.occupied() {
+        let center = (Vec2::new(x as f32, y as f32) + 0.5) * index.cell_size;
+        gizmos.rect_2d(center, 0.0, size, Color::rgba(0.5, 0.5, 1.0, 0.3));
+    }
+}
+
+/// each field in its own color, brighter where it's stronger
+pub fn draw_scents(overlays: Res<Overlays>, scents: Res<Scents>, mut gizmos: Gizmos) {
+    if !overlays.scent {
+        return;
+    }
+    let fields = [
+        (&scents.food, Color::ORANGE),
+        (&scents.alarm, Color::YELLOW),
+        (&scents.shark, Color::RED),
+    ];
+    for (field, color) in fields {
+        let size = Vec2::splat(field.cell_size);
+        for (center, value) in field.cells(0.01) {
+            let alpha = value.min(1.0) * 0.6;
+            gizmos.rect_2d(center, 0.0, size, color.with_a(alpha));
+        }
+    }
+}
+
+/// an arrow every few cells showing which way the water moves, scaled up to be visible
+pub fn draw_flow(overlays: Res<Overlays>, tank: Res<Tank>, flow: Res<Flow>, mut gizmos: Gizmos) {
+    if !overlays.flow || flow.is_still() {
+        return;
+    }
+    let [minx, maxx, miny, maxy] = tank.bounds();
+    let spacing = 40.0;
+    let mut y = miny + spacing / 2.0;
+    while y < maxy {
+        let mut x = minx + spacing / 2.0;
+        while x < maxx {
+            let p = Vec2::new(x, y);
+            let v = flow.at(p) * spacing / 2.0;
+            gizmos.arrow_2d(p, p + v, Color::rgba(0.3, 0.7, 1.0, 0.5));
+            x += spacing;
+        }
+        y += spacing;
+    }
+}
+
+/// a line per species in the bottom left of the view, scaled to the largest count in the history
+pub fn draw_population(
+    overlays: Res<Overlays>,
+    history: Res<PopulationHistory>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&GlobalTransform, &OrthographicProjection), With<Camera>>,
+    mut gizmos: Gizmos,
+) {
+    if !overlays.population || history.samples.len() < 2 {
+        return;
+    }
+    let (Ok(window), Ok((camera, projection))) = (windows.get_single(), cameras.get_single())
+    else {
+        return;
+    };
+    let scale = projection.scale;
+    let half = Vec2::new(window.width(), window.height()) / 2.0 * scale;
+    let size = Vec2::new(300.0, 120.0) * scale;
+    let corner = camera.translation().truncate() - half + Vec2::splat(20.0 * scale);
+    gizmos.rect_2d(
+        corner + size / 2.0,
+        0.0,
+        size,
+        Color::rgba(1.0, 1.0, 1.0, 0.3),
+    );
+
+    let largest = history
+        .samples
+        .iter()
+        .flatten()
+        .copied()
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let species = history.samples.back().map_or(0, Vec::len);
+    let colors = [
+        Color::CYAN,
+        Color::RED,
+        Color::GREEN,
+        Color::ORANGE,
+        Color::FUCHSIA,
+    ];
+    let step = size.x / (POPULATION_HISTORY - 1) as f32;
+    for s in 0..species {
+        let points = history.samples.iter().enumerate().map(|(i, counts)| {
+            let y = counts.get(s).copied().unwrap_or(0) as f32 / largest as f32;
+            corner + Vec2::new(i as f32 * step, y * size.y)
+        });
+        gizmos.linestrip_2d(points, colors[s % colors.len()]);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::components::TankGeometry;
+use crate::resources::{Tank, TankShape};
+use bevy::prelude::*;
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
+
+const WATER: Color = Color::rgb(0.02, 0.08, 0.16);
+const ROCK: Color = Color::rgb(0.25, 0.22, 0.2);
+const OUTLINE: Color = Color::rgb(0.6, 0.8, 0.9);
+
+/// (re)spawns the water and obstacle meshes whenever the tank geometry changes
+pub fn render_tank(
+    mut commands: Commands,
+    tank: Res<Tank>,
+    existing: Query<Entity, With<TankGeometry>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !tank.is_changed() {
+        return;
+    }
+    for e in &existing {
+        commands.entity(e).despawn();
+    }
+
+    let (water, center) = match tank.shape {
+        TankShape::Circle { radius } => (meshes.add(Circle::new(radius)), Vec2::ZERO),
+        TankShape::Rectangle {
+            bounds: [minx, maxx, miny, maxy],
+        } => (
+            meshes.add(Rectangle::new(maxx - minx, maxy - miny)),
+            Vec2::new((minx + maxx) / 2.0, (miny + maxy) / 2.0),
+        ),
+    };
+    commands.spawn((
+        TankGeometry,
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(water),
+            material: materials.add(WATER),
+            transform: Transform::from_xyz(center.x, center.y, 0.0),
+            ..default()
+        },
+    ));
+
+    let rock = materials.add(ROCK);
+    for o in &tank.obstacles {
+        commands.spawn((
+            TankGeometry,
+            MaterialMesh2dBundle {
+                mesh: Mesh2dHandle(meshes.add(Circle::new(o.radius))),
+                material: rock.clone(),
+                transform: Transform::from_xyz(o.center.x, o.center.y, 0.1),
+                ..default()
+            },
+        ));
+    }
+}
+
+pub fn outline_tank(tank: Res<Tank>, mut gizmos: Gizmos) {
+    match tank.shape {
+        TankShape::Circle { radius } => {
+            gizmos.circle_2d(Vec2::ZERO, radius, OUTLINE);
+        }
+        TankShape::Rectangle {
+            bounds: [minx, maxx, miny, maxy],
+        } => {
+            gizmos.rect_2d(
+                Vec2::new((minx + maxx) / 2.0, (miny + maxy) / 2.0),
+                0.0,
+                Vec2::new(maxx - minx, maxy - miny),
+                OUTLINE,
+            );
+        }
+    }
+    for o in &tank.obstacles {
+        gizmos.circle_2d(o.center, o.radius, OUTLINE);
+    }
+}
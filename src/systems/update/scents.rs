@@ -0,0 +1,27 @@
+use crate::components::{Food, IsShark, Position};
+use crate::constants::{FOOD_SCENT, SHARK_SCENT, TIME_RATE};
+use crate::resources::{Scents, Tank};
+use bevy::prelude::{Query, Res, ResMut, Time, With};
+
+/// food and sharks leave scent where they are, then every field spreads and fades
+pub fn diffuse_scents(
+    time: Res<Time>,
+    tank: Res<Tank>,
+    mut scents: ResMut<Scents>,
+    food: Query<&Position, With<Food>>,
+    sharks: Query<&Position, IsShark>,
+) {
+    let ticks = time.delta().as_secs_f32() * TIME_RATE;
+    for field in scents.fields_mut() {
+        field.fit(tank.bounds());
+    }
+    for p in &food {
+        scents.food.emit(p.0, FOOD_SCENT * ticks);
+    }
+    for p in &sharks {
+        scents.shark.emit(p.0, SHARK_SCENT * ticks);
+    }
+    for field in scents.fields_mut() {
+        field.step(ticks);
+    }
+}
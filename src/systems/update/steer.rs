@@ -0,0 +1,36 @@
+use crate::components::{Motion, Rotation, Speed, Steering, TurnRate};
+use crate::constants::TIME_RATE;
+use bevy::math::Vec2;
+use bevy::prelude::{Query, Res, Time};
+
+pub fn clear_steering(mut swimmers: Query<&mut Steering>) {
+    for mut steering in &mut swimmers {
+        steering.clear();
+    }
+}
+
+/// turns every swimmer once, by however much its rules add up to within its turn rate
+pub fn steer(mut swimmers: Query<(&mut Rotation, &TurnRate, &mut Steering)>) {
+    for (mut r, tr, mut steering) in &mut swimmers {
+        steering.from = *r;
+        steering.turn = steering.resolve(*r, tr.0);
+        *r += Rotation::new(steering.turn);
+    }
+}
+
+/// reynolds kinematics: steering accelerates the velocity, and the heading follows the velocity
+pub fn accelerate(
+    time: Res<Time>,
+    mut swimmers: Query<(&mut Rotation, &Speed, &mut Motion, &mut Steering)>,
+) {
+    let dt = time.delta().as_secs_f32() * TIME_RATE;
+    for (mut r, s, mut m, mut steering) in &mut swimmers {
+        let force = steering.force(m.velocity, s.0, m.max_force);
+        m.velocity = (m.velocity + force * dt).clamp_length_max(s.0);
+        steering.from = *r;
+        if m.velocity != Vec2::ZERO {
+            *r = Rotation::new(m.velocity.to_angle());
+        }
+        steering.turn = (*r - steering.from).0;
+    }
+}
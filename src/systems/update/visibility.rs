@@ -0,0 +1,53 @@
+use crate::components::{Fleeing, Position, Rotation, Size, Vision};
+use crate::resources::{SpatialIndex, Tank, VisibilityMap};
+use crate::utils::can_see_position;
+use bevy::math::Vec2;
+use bevy::prelude::{Entity, Query, Res, ResMut};
+
+/// fills the visibility map from the spatial index. fleeing fish ignore everything but the shark
+/// so they don't get an entry
+#[allow(clippy::type_complexity)]
+pub fn compute_visibility(
+    index: Res<SpatialIndex>,
+    tank: Res<Tank>,
+    mut visibility: ResMut<VisibilityMap>,
+    swimmers: Query<(
+        Entity,
+        &Size,
+        &Position,
+        &Rotation,
+        &Vision,
+        Option<&Fleeing>,
+    )>,
+) {
+    visibility.clear();
+    // distance counts for more the bigger the fish being looked at, so small fish are visible
+    // from further away and the smallest sets how far to search. never past the far side of the
+    // tank though, a tiny fish would have every swimmer search everywhere
+    let smallest = swimmers
+        .iter()
+        .map(|(_, s, _, _, _, _)| s.0)
+        .fold(f32::INFINITY, f32::min);
+    let [minx, maxx, miny, maxy] = tank.bounds();
+    let diagonal = Vec2::new(maxx - minx, maxy - miny).length();
+    for (e1, _, p1, r1, v1, f1) in &swimmers {
+        if f1.is_some_and(|f| f.active) {
+            continue;
+        }
+        let mut visible = Vec::new();
+        for (e2, _) in index.nearby(p1.0, (v1.distance / smallest).min(diagonal)) {
+            if e2 == e1 {
+                continue;
+            }
+            let Ok((_, s2, p2, _, _, _)) = swimmers.get(e2) else {
+                continue;
+            };
+            if can_see_position(*p1, *r1, *v1, *s2, *p2) {
+                visible.push(e2);
+            }
+        }
+        if !visible.is_empty() {
+            visibility.insert(e1, visible);
+        }
+    }
+}
@@ -1,7 +1,7 @@
-use crate::components::{Position, Rotation, Size, Vision};
-use crate::constants::{BOUNDS, RADIUS};
+use crate::components::{Body, Position, Rotation, Size, Vision};
+use crate::resources::Obstacle;
 use bevy::math::Vec2;
-use rand::random;
+use rand::Rng;
 use std::f32::consts::{PI, TAU};
 use std::ops::{Add, AddAssign, Deref, Mul};
 
@@ -14,8 +14,8 @@ pub enum Direction {
 }
 
 impl Direction {
-    pub fn next() -> Direction {
-        let r = random::<f32>() * 6.0;
+    pub fn next(rng: &mut impl Rng) -> Direction {
+        let r = rng.gen::<f32>() * 6.0;
         if r < 4.0 {
             Direction::Straight
         } else if r < 5.0 {
@@ -36,8 +36,14 @@ pub fn normalize_radians(r: f32) -> f32 {
     }
 }
 
-pub fn random_in_range(min: f32, max: f32) -> f32 {
-    random::<f32>() * (max - min) + min
+pub fn random_in_range(min: f32, max: f32, rng: &mut impl Rng) -> f32 {
+    rng.gen::<f32>() * (max - min) + min
+}
+
+/// a sample from the standard normal distribution, by box-muller
+pub fn random_normal(rng: &mut impl Rng) -> f32 {
+    let u = 1.0 - rng.gen::<f32>();
+    (-2.0 * u.ln()).sqrt() * (TAU * rng.gen::<f32>()).cos()
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -88,8 +94,8 @@ pub fn can_see_position(p1: Position, r1: Rotation, v1: Vision, s2: Size, p2: Po
 
 // returns (left, right, top, bottom)
 // returns 0 if facing away from that wall
-pub fn distance_to_walls(p: Position, r: Rotation) -> (f32, f32, f32, f32) {
-    let [minx, maxx, miny, maxy] = BOUNDS;
+pub fn distance_to_walls(p: Position, r: Rotation, bounds: [f32; 4]) -> (f32, f32, f32, f32) {
+    let [minx, maxx, miny, maxy] = bounds;
     let left = p.x - minx;
     let right = maxx - p.x;
     let top = maxy - p.y;
@@ -112,15 +118,228 @@ pub fn distance_to_walls(p: Position, r: Rotation) -> (f32, f32, f32, f32) {
 }
 
 // https://www.bluebill.net/circle_ray_intersection.html
-pub fn distance_to_circle_wall(p: Position, r: Rotation) -> f32 {
-    let C = Vec2::ZERO;
-    let P = p.0;
-    let V = r.unit_vector();
-    let U = C - P;
-    let U1 = U.dot(V) * V;
-    let U2 = U - U1;
-    let d = U2.length();
-    let m = (RADIUS.powi(2) - d.powi(2)).sqrt();
-    let P1 = P + U1 + m * V;
-    p.distance(Position(P1))
+pub fn distance_to_circle_wall(p: Position, r: Rotation, radius: f32) -> f32 {
+    let c = Vec2::ZERO;
+    let v = r.unit_vector();
+    let u = c - p.0;
+    let u1 = u.dot(v) * v;
+    let u2 = u - u1;
+    let d = u2.length();
+    // a swimmer pushed just past the wall would take the root of a negative
+    let m = (radius.powi(2) - d.powi(2)).max(0.0).sqrt();
+    let p1 = p.0 + u1 + m * v;
+    p.distance(Position(p1))
+}
+
+// distance along a ray from inside the rectangle to where it leaves through one of the walls
+pub fn distance_to_square_wall(p: Position, r: Rotation, bounds: [f32; 4]) -> f32 {
+    let [minx, maxx, miny, maxy] = bounds;
+    let v = r.unit_vector();
+    let tx = if v.x > 0.0 {
+        (maxx - p.x) / v.x
+    } else if v.x < 0.0 {
+        (minx - p.x) / v.x
+    } else {
+        f32::INFINITY
+    };
+    let ty = if v.y > 0.0 {
+        (maxy - p.y) / v.y
+    } else if v.y < 0.0 {
+        (miny - p.y) / v.y
+    } else {
+        f32::INFINITY
+    };
+    tx.min(ty).max(0.0)
+}
+
+// same construction as distance_to_circle_wall, but from outside the circle so the near
+// intersection is the one that matters. returns infinity if the ray misses or points away
+pub fn distance_to_obstacle(p: Position, r: Rotation, obstacle: Obstacle) -> f32 {
+    let v = r.unit_vector();
+    let u = obstacle.center - p.0;
+    let along = u.dot(v);
+    if along < 0.0 {
+        return f32::INFINITY;
+    }
+    let d = (u - along * v).length();
+    if d > obstacle.radius {
+        return f32::INFINITY;
+    }
+    let m = (obstacle.radius.powi(2) - d.powi(2)).sqrt();
+    (along - m).max(0.0)
+}
+
+/// the corners of a swimmer's body in world space
+pub fn body(p: Position, r: Rotation, s: Size, b: Body) -> [Vec2; 3] {
+    b.0.map(|v| p.0 + Vec2::from_angle(r.0).rotate(v * s.0))
+}
+
+/// distance from the center to the furthest corner of a swimmer's body
+pub fn reach(s: Size, b: Body) -> f32 {
+    b.0.iter().map(|v| v.length()).fold(0.0, f32::max) * s.0
+}
+
+fn project(polygon: &[Vec2], axis: Vec2) -> (f32, f32) {
+    polygon
+        .iter()
+        .map(|v| v.dot(axis))
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), d| {
+            (min.min(d), max.max(d))
+        })
+}
+
+// separating axis test for convex polygons. returns the shortest vector that moves a out of b, or
+// None if they don't overlap
+pub fn overlap(a: &[Vec2], b: &[Vec2]) -> Option<Vec2> {
+    let mut depth = f32::INFINITY;
+    let mut axis = Vec2::ZERO;
+    for polygon in [a, b] {
+        for (i, v) in polygon.iter().enumerate() {
+            let edge = polygon[(i + 1) % polygon.len()] - *v;
+            let normal = edge.perp().normalize_or_zero();
+            let (amin, amax) = project(a, normal);
+            let (bmin, bmax) = project(b, normal);
+            let d = amax.min(bmax) - amin.max(bmin);
+            if d <= 0.0 {
+                return None;
+            }
+            if d < depth {
+                depth = d;
+                axis = normal;
+            }
+        }
+    }
+    let centroid = |polygon: &[Vec2]| polygon.iter().sum::<Vec2>() / polygon.len() as f32;
+    if (centroid(a) - centroid(b)).dot(axis) < 0.0 {
+        axis = -axis;
+    }
+    Some(axis * depth)
+}
+
+/// whether a point is inside a convex polygon, whichever way round its corners go
+pub fn contains(polygon: &[Vec2], point: Vec2) -> bool {
+    let sides = polygon.iter().enumerate().map(|(i, v)| {
+        let edge = polygon[(i + 1) % polygon.len()] - *v;
+        edge.perp_dot(point - *v)
+    });
+    let (mut left, mut right) = (false, false);
+    for side in sides {
+        left |= side > 0.0;
+        right |= side < 0.0;
+    }
+    !(left && right)
+}
+
+/// where a pursuer at p with the given speed can meet a target at q moving with velocity v, if it
+/// can catch it at all
+pub fn intercept(p: Position, speed: f32, q: Position, v: Vec2) -> Option<Position> {
+    // solve |q + v t - p| = speed t for the earliest t >= 0
+    let d = q.0 - p.0;
+    let a = v.length_squared() - speed * speed;
+    let b = 2.0 * d.dot(v);
+    let c = d.length_squared();
+    let t = if a.abs() < f32::EPSILON {
+        // same speed as the target, only catchable if it's coming closer
+        (b < 0.0).then(|| -c / b)?
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+            .into_iter()
+            .filter(|t| *t >= 0.0)
+            .fold(None, |best: Option<f32>, t| {
+                Some(best.map_or(t, |b| b.min(t)))
+            })?
+    };
+    Some(Position(q.0 + v * t))
+}
+
+// a fixed pseudo random unit vector for each lattice point
+fn lattice_gradient(x: i32, y: i32) -> Vec2 {
+    let mut h = (x as u32).wrapping_mul(0x8da6_b343) ^ (y as u32).wrapping_mul(0xd816_3841);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0x5bd1_e995);
+    h ^= h >> 15;
+    Vec2::from_angle(h as f32 / u32::MAX as f32 * TAU)
+}
+
+/// smooth gradient noise, roughly in [-1, 1], varying over about one unit
+pub fn perlin(p: Vec2) -> f32 {
+    let cell = p.floor();
+    let f = p - cell;
+    let (x, y) = (cell.x as i32, cell.y as i32);
+    let fade = |t: f32| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+    let corner = |dx: i32, dy: i32| {
+        lattice_gradient(x + dx, y + dy).dot(f - Vec2::new(dx as f32, dy as f32))
+    };
+    let (u, v) = (fade(f.x), fade(f.y));
+    let bottom = corner(0, 0) + u * (corner(1, 0) - corner(0, 0));
+    let top = corner(0, 1) + u * (corner(1, 1) - corner(0, 1));
+    (bottom + v * (top - bottom)) * std::f32::consts::SQRT_2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(center: Vec2, half: f32) -> [Vec2; 4] {
+        [
+            center + Vec2::new(-half, -half),
+            center + Vec2::new(half, -half),
+            center + Vec2::new(half, half),
+            center + Vec2::new(-half, half),
+        ]
+    }
+
+    #[test]
+    fn overlap_pushes_out() {
+        let a = square(Vec2::new(1.5, 0.2), 1.0);
+        let b = square(Vec2::ZERO, 1.0);
+        // the shallow way out, away from b
+        let push = overlap(&a, &b).unwrap();
+        assert!((push - Vec2::new(0.5, 0.0)).length() < 1e-5);
+        let moved = a.map(|v| v + push * 1.01);
+        assert!(overlap(&moved, &b).is_none());
+        // and the other way round for b
+        assert!((overlap(&b, &a).unwrap() + push).length() < 1e-5);
+    }
+
+    #[test]
+    fn overlap_apart() {
+        let a = square(Vec2::new(3.0, 0.0), 1.0);
+        assert!(overlap(&a, &square(Vec2::ZERO, 1.0)).is_none());
+    }
+
+    #[test]
+    fn intercept_still_target() {
+        let q = Position::new(30.0, 40.0);
+        let meet = intercept(Position::new(0.0, 0.0), 2.0, q, Vec2::ZERO).unwrap();
+        assert!(meet.distance(q) < 1e-4);
+    }
+
+    #[test]
+    fn intercept_moving_target() {
+        let (p, q, v, speed) = (
+            Position::new(0.0, 0.0),
+            Position::new(100.0, 0.0),
+            Vec2::Y,
+            2.0,
+        );
+        let meet = intercept(p, speed, q, v).unwrap();
+        // both get there at the same time
+        let t = meet.distance(q) / v.length();
+        assert!((p.distance(meet) - speed * t).abs() < 1e-3);
+    }
+
+    #[test]
+    fn intercept_faster_target_getting_away() {
+        let p = Position::new(0.0, 0.0);
+        assert!(intercept(p, 1.0, Position::new(10.0, 0.0), Vec2::X * 2.0).is_none());
+        // same speed, only catchable head on
+        assert!(intercept(p, 1.0, Position::new(10.0, 0.0), Vec2::X).is_none());
+        assert!(intercept(p, 1.0, Position::new(10.0, 0.0), -Vec2::X).is_some());
+    }
 }